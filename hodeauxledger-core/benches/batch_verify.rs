@@ -0,0 +1,69 @@
+//! One-by-one vs batched signature verification over a synthetic scope.
+//!
+//! `cargo bench -p hodeauxledger-core --bench batch_verify`
+//! (set `RHEX_BENCH_RECORDS` to change the chain length, default 100k).
+
+use std::time::Instant;
+
+use hodeauxledger_core::rhex::{batch::validate_all, chain::verify_chain};
+use hodeauxledger_core::{Intent, Key, Rhex, Signature};
+
+fn build_chain(count: usize) -> Vec<Rhex> {
+    let author = Key::from_bytes(&[42u8; 32]);
+    let mut previous_hash = [0u8; 32];
+    let mut out = Vec::with_capacity(count);
+    for i in 0..count {
+        let mut rhex = Rhex::draft(Intent::new(
+            &previous_hash,
+            "bench",
+            &format!("{i:016}"),
+            &author.to_bytes(),
+            &[0u8; 32],
+            "record:text",
+            serde_json::json!({ "text": format!("record {i}") }),
+        ));
+        rhex.context.at = i as u64 + 1;
+        let pre = rhex.author_prehash().unwrap();
+        rhex.signatures.push(Signature {
+            sig_type: 0,
+            public_key: author.to_bytes(),
+            sig: author.sign(&pre).unwrap().to_bytes(),
+        });
+        let rhex = rhex.finalize().unwrap();
+        previous_hash = rhex.current_hash.unwrap();
+        out.push(rhex);
+    }
+    out
+}
+
+fn main() {
+    let count = std::env::var("RHEX_BENCH_RECORDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100_000);
+
+    let start = Instant::now();
+    let records = build_chain(count);
+    println!("built {count} records in {:?}", start.elapsed());
+
+    let start = Instant::now();
+    for rhex in &records {
+        rhex.validate().unwrap();
+    }
+    let single = start.elapsed();
+    println!("one by one:   {single:?}");
+
+    let start = Instant::now();
+    assert!(validate_all(&records).is_empty());
+    let batched = start.elapsed();
+    println!("batched:      {batched:?}");
+
+    let start = Instant::now();
+    assert!(verify_chain("bench", &records).is_ok());
+    println!("verify_chain: {:?}", start.elapsed());
+
+    println!(
+        "speedup: {:.2}x",
+        single.as_secs_f64() / batched.as_secs_f64()
+    );
+}
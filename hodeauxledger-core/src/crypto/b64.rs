@@ -20,3 +20,35 @@ pub fn from_base64_to_64(s: &str) -> Result<[u8; 64], anyhow::Error> {
     let v = from_base64(s)?;
     <[u8; 64]>::try_from(v.as_slice()).map_err(|_| anyhow!("decoded value not 64 bytes"))
 }
+
+/// `#[serde(with = "b64_32")]` for 32-byte values carried in 📊 as base64.
+pub mod b64_32 {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&super::to_base64(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(d)?;
+        super::from_base64_to_32(&s).map_err(D::Error::custom)
+    }
+}
+
+/// Same as [`b64_32`] for optional fields.
+pub mod b64_32_opt {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(bytes: &Option<[u8; 32]>, s: S) -> Result<S::Ok, S::Error> {
+        match bytes {
+            Some(b) => s.serialize_some(&super::to_base64(b)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<[u8; 32]>, D::Error> {
+        Option::<String>::deserialize(d)?
+            .map(|s| super::from_base64_to_32(&s).map_err(D::Error::custom))
+            .transpose()
+    }
+}
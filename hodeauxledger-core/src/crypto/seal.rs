@@ -0,0 +1,217 @@
+//! Sealed 📊 payloads for confidential records.
+//!
+//! The plaintext is encrypted once under a random content key. That key is
+//! then wrapped for every recipient with a key derived from an X25519
+//! exchange between a one-off ephemeral key and the recipient's ed25519
+//! ledger key (converted to its Montgomery form). Only the sealed form ever
+//! lands in the record, so it is what gets hashed and signed.
+
+use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead, aead::Payload};
+use anyhow::{Result, anyhow, bail};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::crypto::b64::{from_base64, from_base64_to_32, to_base64};
+use crate::key::key::Key;
+
+/// Algorithm tag stored alongside the ciphertext.
+pub const SEAL_ALG: &str = "x25519-blake3-aes256gcm";
+const KEK_CONTEXT: &str = "hodeauxledger 2025 seal v1 key wrap";
+const NONCE_LEN: usize = 12;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SealedData {
+    #[serde(rename = "alg", alias = "algorithm")]
+    pub alg: String,
+    /// Ephemeral X25519 public key.
+    #[serde(rename = "epk", alias = "ephemeral_public_key")]
+    pub epk: String,
+    #[serde(rename = "n", alias = "nonce")]
+    pub nonce: String,
+    #[serde(rename = "ct", alias = "ciphertext")]
+    pub ciphertext: String,
+    #[serde(rename = "to", alias = "recipients")]
+    pub recipients: Vec<SealedRecipient>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SealedRecipient {
+    /// Recipient's ed25519 ledger public key.
+    #[serde(rename = "pk", alias = "public_key")]
+    pub public_key: String,
+    /// Content key wrapped for this recipient.
+    #[serde(rename = "wk", alias = "wrapped_key")]
+    pub wrapped_key: String,
+}
+
+impl SealedData {
+    pub fn from_value(value: &serde_json::Value) -> Result<Self> {
+        let sealed: SealedData = serde_json::from_value(value.clone())?;
+        if sealed.alg != SEAL_ALG {
+            bail!("unsupported seal algorithm {}", sealed.alg);
+        }
+        Ok(sealed)
+    }
+
+    pub fn to_value(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    /// Is `public_key` one of the recipients?
+    pub fn is_recipient(&self, public_key: &[u8; 32]) -> bool {
+        let pk = to_base64(public_key);
+        self.recipients.iter().any(|r| r.public_key == pk)
+    }
+}
+
+/// Encrypt `data` to every ed25519 public key in `recipients`.
+/// `aad` is bound to the ciphertext and must be given again to open it.
+pub fn seal(data: &serde_json::Value, recipients: &[[u8; 32]], aad: &[u8]) -> Result<SealedData> {
+    if recipients.is_empty() {
+        bail!("at least one recipient is required");
+    }
+
+    let mut cek = random32()?;
+    let mut eph = random32()?;
+    let epk = MontgomeryPoint::mul_base_clamped(eph);
+
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::fill(&mut nonce).map_err(|e| anyhow!("randomness failed: {e}"))?;
+
+    let plaintext = serde_json::to_vec(data)?;
+    let ciphertext = Aes256Gcm::new_from_slice(&cek)
+        .map_err(|_| anyhow!("bad content key"))?
+        .encrypt(
+            &nonce.into(),
+            Payload {
+                msg: &plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| anyhow!("encryption failed"))?;
+
+    let mut wrapped = Vec::with_capacity(recipients.len());
+    for pk in recipients {
+        let point = VerifyingKey::from_bytes(pk)
+            .map_err(|_| anyhow!("invalid recipient key {}", to_base64(pk)))?
+            .to_montgomery();
+        let shared = point.mul_clamped(eph);
+        let mut kek = derive_kek(&shared, &epk, pk);
+        // The KEK is unique per (ephemeral, recipient) so a fixed nonce is fine.
+        let wk = Aes256Gcm::new_from_slice(&kek)
+            .map_err(|_| anyhow!("bad wrap key"))?
+            .encrypt(&[0u8; NONCE_LEN].into(), cek.as_ref())
+            .map_err(|_| anyhow!("key wrap failed"))?;
+        kek.zeroize();
+        wrapped.push(SealedRecipient {
+            public_key: to_base64(pk),
+            wrapped_key: to_base64(&wk),
+        });
+    }
+
+    cek.zeroize();
+    eph.zeroize();
+
+    Ok(SealedData {
+        alg: SEAL_ALG.to_string(),
+        epk: to_base64(epk.as_bytes()),
+        nonce: to_base64(&nonce),
+        ciphertext: to_base64(&ciphertext),
+        recipients: wrapped,
+    })
+}
+
+/// Decrypt a sealed payload with one of the recipient keys.
+pub fn open(sealed: &SealedData, key: &Key, aad: &[u8]) -> Result<serde_json::Value> {
+    let sk = key
+        .sk
+        .as_ref()
+        .ok_or_else(|| anyhow!("no private key available for decryption"))?;
+    let pk = sk.verifying_key().to_bytes();
+    let pk_b64 = to_base64(&pk);
+    let recipient = sealed
+        .recipients
+        .iter()
+        .find(|r| r.public_key == pk_b64)
+        .ok_or_else(|| anyhow!("key {} is not a recipient", pk_b64))?;
+
+    let epk = MontgomeryPoint(from_base64_to_32(&sealed.epk)?);
+    let mut scalar = sk.to_scalar_bytes();
+    let shared = epk.mul_clamped(scalar);
+    scalar.zeroize();
+
+    let mut kek = derive_kek(&shared, &epk, &pk);
+    let wk = from_base64(&recipient.wrapped_key)?;
+    let cek = Aes256Gcm::new_from_slice(&kek)
+        .map_err(|_| anyhow!("bad wrap key"))?
+        .decrypt(&[0u8; NONCE_LEN].into(), wk.as_ref())
+        .map_err(|_| anyhow!("key unwrap failed"))?;
+    kek.zeroize();
+
+    let nonce: [u8; NONCE_LEN] = from_base64(&sealed.nonce)?
+        .try_into()
+        .map_err(|_| anyhow!("nonce not {} bytes", NONCE_LEN))?;
+    let ciphertext = from_base64(&sealed.ciphertext)?;
+    let plaintext = Aes256Gcm::new_from_slice(&cek)
+        .map_err(|_| anyhow!("bad content key"))?
+        .decrypt(
+            &nonce.into(),
+            Payload {
+                msg: &ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| anyhow!("decryption failed"))?;
+
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn derive_kek(shared: &MontgomeryPoint, epk: &MontgomeryPoint, recipient: &[u8; 32]) -> [u8; 32] {
+    let mut h = blake3::Hasher::new_derive_key(KEK_CONTEXT);
+    h.update(shared.as_bytes());
+    h.update(epk.as_bytes());
+    h.update(recipient);
+    h.finalize().into()
+}
+
+fn random32() -> Result<[u8; 32]> {
+    let mut out = [0u8; 32];
+    getrandom::fill(&mut out).map_err(|e| anyhow!("randomness failed: {e}"))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Intent, Key, Rhex};
+
+    #[test]
+    fn seal_and_open_roundtrip() {
+        let alice = Key::from_bytes(&[3u8; 32]);
+        let bob = Key::from_bytes(&[4u8; 32]);
+        let eve = Key::from_bytes(&[5u8; 32]);
+        let data = serde_json::json!({ "text": "for your eyes only" });
+        let intent = Intent::new(
+            &[0u8; 32],
+            "core",
+            "AAAAAAAAAAAAAAAA",
+            &alice.to_bytes(),
+            &[0u8; 32],
+            "record:text",
+            data.clone(),
+        );
+        let mut rhex = Rhex::draft(intent);
+        rhex.seal_data(&[alice.to_bytes(), bob.to_bytes()]).unwrap();
+
+        assert!(rhex.magic().unwrap().is_encrypted());
+        assert_ne!(rhex.intent.data, data);
+        assert_eq!(rhex.open_data(&alice).unwrap(), data);
+        assert_eq!(rhex.open_data(&bob).unwrap(), data);
+        assert!(rhex.open_data(&eve).is_err());
+
+        // Moving the ciphertext to another scope breaks it.
+        rhex.intent.scope = "elsewhere".into();
+        assert!(rhex.open_data(&bob).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::{Result, bail};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use getrandom;
 
+use super::revocation::Revocation;
 use crate::to_base64;
 
 pub struct Key {
@@ -10,6 +11,8 @@ pub struct Key {
     pub pk: Option<VerifyingKey>,
     pub effective_micromark: Option<u64>,
     pub expires_micromark: Option<u64>,
+    /// Set once a key:revoke for the key has been seen.
+    pub revoked: Option<Revocation>,
 }
 
 impl Key {
@@ -20,6 +23,7 @@ impl Key {
             pk: None,
             effective_micromark: None,
             expires_micromark: None,
+            revoked: None,
         }
     }
 
@@ -34,6 +38,7 @@ impl Key {
             pk: Some(pk),
             effective_micromark: None,
             expires_micromark: None,
+            revoked: None,
         }
     }
 
@@ -76,6 +81,7 @@ impl Key {
             pk: Some(pk),
             effective_micromark: None,
             expires_micromark: None,
+            revoked: None,
         }
     }
 
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Why a key was revoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevokeReason {
+    /// Someone else may hold the secret key.
+    Compromised,
+    /// Replaced by a newer key.
+    Superseded,
+    /// No longer needed.
+    Retired,
+}
+
+impl RevokeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevokeReason::Compromised => "compromised",
+            RevokeReason::Superseded => "superseded",
+            RevokeReason::Retired => "retired",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "compromised" => Some(RevokeReason::Compromised),
+            "superseded" => Some(RevokeReason::Superseded),
+            "retired" => Some(RevokeReason::Retired),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RevokeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A key:revoke as it applies to the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revocation {
+    pub reason: RevokeReason,
+    /// Micromark from which the key may no longer sign.
+    pub effective_micromark: u64,
+    /// For compromised keys, when the compromise may have started.
+    pub compromised_micromark: Option<u64>,
+    /// ⬇️🧬 of the key:revoke record.
+    pub revoke_ref: Option<[u8; 32]>,
+}
+
+impl Revocation {
+    /// Is the key revoked at micromark `at`?
+    pub fn in_force(&self, at: u64) -> bool {
+        at >= self.effective_micromark
+    }
+
+    /// When records signed by the key stop being trustworthy: the start of
+    /// a compromise, or never for other reasons.
+    pub fn suspect_from(&self) -> Option<u64> {
+        match self.reason {
+            RevokeReason::Compromised => Some(
+                self.compromised_micromark
+                    .unwrap_or(self.effective_micromark),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Should a record the key signed at `at` be treated as suspect?
+    pub fn is_suspect(&self, at: u64) -> bool {
+        self.suspect_from().is_some_and(|from| at >= from)
+    }
+}
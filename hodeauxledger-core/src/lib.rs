@@ -1,6 +1,7 @@
 pub mod crypto;
 pub mod key;
 pub mod policy;
+pub mod record;
 pub mod rhex;
 pub mod schema;
 pub mod scope;
@@ -11,9 +12,12 @@ pub mod url;
 pub use crypto::b64::{from_base64, to_base64};
 pub use key::key::Key;
 pub use rhex::context::Context;
+pub use rhex::error::RhexError;
 pub use rhex::intent::Intent;
 pub use rhex::rhex::Rhex;
 pub use rhex::signature::Signature;
 pub use scope::alias::Alias;
+pub use scope::name::ScopeName;
+pub use time::gt::{GtDuration, GtTime};
 pub use time::time::GTClock;
 pub use url::url::RhexUrl;
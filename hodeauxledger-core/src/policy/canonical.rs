@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
+use serde_json::{Map, Value, json};
+
+use super::{default::Default, error::PolicyError, policy::Policy, rule::Rule};
+use crate::record::record_type::RecordType;
+
+// Accepted names for each field, canonical (emoji) first, then long and
+// short ones from docs/schema/policy_set@0.md.
+const SCHEMA: &[&str] = &["schema", "sch"];
+const SCOPE: &[&str] = &["🌐", "scope"];
+const DEFAULTS: &[&str] = &["🧱", "defaults", "d"];
+const RULES: &[&str] = &["⛓️", "rules", "r"];
+const QUORUM_TTL: &[&str] = &["🤝⏳", "quorum_ttl", "qt"];
+const EFFECTIVE: &[&str] = &["🟢🕑", "effective_micromark", "eff"];
+const EXPIRES: &[&str] = &["🔴🕑", "expiration_micromark", "expires_micromark", "exp"];
+const NOTE: &[&str] = &["🗒️", "note"];
+const MAX_RECORD: &[&str] = &["📏📦", "max_record_bytes", "mrb"];
+const MAX_DATA: &[&str] = &["📏📊", "max_data_bytes", "mdb"];
+const INHERIT: &[&str] = &["⬆️", "inherit", "inh"];
+
+const RECORD_TYPES: &[&str] = &["📄", "record_types", "record_type", "rt"];
+const ROLES: &[&str] = &["🥐", "roles", "append_roles"];
+const QUORUM_K: &[&str] = &["🤝☝️", "quorum_k"];
+const QUORUM_ROLES: &[&str] = &["🤝🥐", "quorum_roles"];
+const RATE: &[&str] = &["↔️", "rate_per_mark"];
+const DENY: &[&str] = &["⛔", "deny"];
+
+/// 📄 may be one type or a group of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            OneOrMany::One(s) => vec![s],
+            OneOrMany::Many(v) => v,
+        }
+    }
+}
+
+/// Pulls fields out of a JSON object by any of their names, noting
+/// problems instead of stopping at the first one.
+struct Reader<'a> {
+    map: &'a Map<String, Value>,
+    at: String,
+    used: Vec<&'a str>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(value: &'a Value, at: &str, errors: &mut PolicyError) -> Option<Self> {
+        match value.as_object() {
+            Some(map) => Some(Self {
+                map,
+                at: at.to_string(),
+                used: Vec::new(),
+            }),
+            None => {
+                errors.push(at, "expected an object");
+                None
+            }
+        }
+    }
+
+    fn path(&self, name: &str) -> String {
+        if self.at.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{name}", self.at)
+        }
+    }
+
+    /// Mark fields as known without reading them.
+    fn skip(&mut self, names: &[&str]) {
+        for (k, _) in self.map.iter().filter(|(k, _)| names.contains(&k.as_str())) {
+            self.used.push(k);
+        }
+    }
+
+    /// First of `names` present and not null, parsed as `T`.
+    fn get<T: DeserializeOwned>(&mut self, names: &[&str], errors: &mut PolicyError) -> Option<T> {
+        let mut found: Option<(&str, &Value)> = None;
+        for (k, v) in self.map.iter().filter(|(k, _)| names.contains(&k.as_str())) {
+            self.used.push(k);
+            match found {
+                Some((first, _)) => errors.push(self.path(k), format!("already given as {first}")),
+                None => found = Some((k, v)),
+            }
+        }
+        let (k, v) = found?;
+        if v.is_null() {
+            return None;
+        }
+        match serde_json::from_value(v.clone()) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                errors.push(self.path(k), e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Report anything that wasn't read.
+    fn finish(self, errors: &mut PolicyError) {
+        for k in self.map.keys() {
+            if !self.used.contains(&k.as_str()) {
+                errors.push(self.path(k), "unknown field");
+            }
+        }
+    }
+}
+
+impl Policy {
+    /// Read a 📜 written with emoji, long or short field names. Rules may
+    /// group several 📄 under one set of settings; they're expanded to
+    /// one [`Rule`] per type, and settings a group leaves out come from
+    /// 🧱. Deny (⛔) groups need only 📄. Every problem found is returned.
+    pub fn parse(value: &Value) -> Result<Self, PolicyError> {
+        let mut errors = PolicyError::default();
+        let Some(mut r) = Reader::new(value, "", &mut errors) else {
+            return Err(errors);
+        };
+        r.skip(SCHEMA);
+
+        let mut policy = Policy::new("", Vec::new());
+        policy.scope = r.get(SCOPE, &mut errors).unwrap_or_default();
+        policy.defaults = r.get::<Default>(DEFAULTS, &mut errors);
+        policy.quorum_ttl = r.get(QUORUM_TTL, &mut errors);
+        policy.effective_micromark = r.get(EFFECTIVE, &mut errors);
+        policy.expiration_micromark = r.get(EXPIRES, &mut errors);
+        policy.note = r.get(NOTE, &mut errors);
+        policy.max_record_bytes = r.get(MAX_RECORD, &mut errors);
+        policy.max_data_bytes = r.get(MAX_DATA, &mut errors);
+        policy.inherit = r.get(INHERIT, &mut errors).unwrap_or(true);
+        let groups: Option<Vec<Value>> = r.get(RULES, &mut errors);
+        r.finish(&mut errors);
+
+        let Some(groups) = groups else {
+            if !errors.problems.iter().any(|p| p.path.starts_with("⛓️")) {
+                errors.push("⛓️", "required");
+            }
+            return Err(errors);
+        };
+
+        // (canonical 📄, deny) -> where it was first given
+        let mut seen: HashMap<(String, bool), String> = HashMap::new();
+        for (i, group) in groups.iter().enumerate() {
+            let at = format!("⛓️[{i}]");
+            let Some(mut g) = Reader::new(group, &at, &mut errors) else {
+                continue;
+            };
+            let types = g.get::<OneOrMany>(RECORD_TYPES, &mut errors);
+            let roles = g.get::<Vec<String>>(ROLES, &mut errors);
+            let quorum_k = g.get::<u8>(QUORUM_K, &mut errors);
+            let quorum_roles = g.get::<Vec<String>>(QUORUM_ROLES, &mut errors);
+            let rate = g.get::<u64>(RATE, &mut errors);
+            let deny = g.get::<bool>(DENY, &mut errors).unwrap_or(false);
+            g.finish(&mut errors);
+
+            let types = match types.map(OneOrMany::into_vec) {
+                Some(types) if !types.is_empty() => types,
+                Some(_) => {
+                    errors.push(format!("{at}.📄"), "empty");
+                    continue;
+                }
+                None => {
+                    errors.push(format!("{at}.📄"), "required");
+                    continue;
+                }
+            };
+
+            let d = policy.defaults.as_ref();
+            let template = if deny {
+                Some(Rule::deny(""))
+            } else {
+                match roles.or_else(|| d.map(|d| d.roles.clone())) {
+                    Some(append_roles) => Some(Rule {
+                        record_type: String::new(),
+                        append_roles,
+                        quorum_k: quorum_k.or(d.map(|d| d.quorum_k)).unwrap_or(1),
+                        quorum_roles: quorum_roles
+                            .or_else(|| d.map(|d| d.quorum_roles.clone()))
+                            .unwrap_or_default(),
+                        rate_per_mark: rate.or(d.map(|d| d.rate_per_mark)).unwrap_or(0),
+                        deny: false,
+                    }),
+                    None => {
+                        errors.push(format!("{at}.🥐"), "required when there are no 🧱");
+                        None
+                    }
+                }
+            };
+
+            for (j, name) in types.iter().enumerate() {
+                let path = format!("{at}.📄[{j}]");
+                let rt = match RecordType::parse(name) {
+                    Ok(rt) => rt.to_string(),
+                    Err(e) => {
+                        errors.push(path, e.to_string());
+                        continue;
+                    }
+                };
+                if let Some(first) = seen.get(&(rt.clone(), deny)) {
+                    errors.push(path, format!("{rt} already has a rule at {first}"));
+                    continue;
+                }
+                seen.insert((rt.clone(), deny), path);
+                if let Some(template) = &template {
+                    policy.rules.push(Rule {
+                        record_type: rt,
+                        ..template.clone()
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(policy)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The form `policy:set` records carry: emoji names, unset fields left
+    /// out, and rules with the same settings grouped under one 📄 list in
+    /// the order they first appear. [`Policy::parse`] reads it back to the
+    /// same policy, and writing that again gives the same JSON.
+    pub fn to_canonical(&self) -> Value {
+        let mut groups: Vec<(&Rule, Vec<&str>)> = Vec::new();
+        for rule in &self.rules {
+            let same = |g: &&mut (&Rule, Vec<&str>)| {
+                let r = g.0;
+                r.deny == rule.deny
+                    && (rule.deny
+                        || (r.append_roles == rule.append_roles
+                            && r.quorum_k == rule.quorum_k
+                            && r.quorum_roles == rule.quorum_roles
+                            && r.rate_per_mark == rule.rate_per_mark))
+            };
+            match groups.iter_mut().find(same) {
+                Some(g) => g.1.push(&rule.record_type),
+                None => groups.push((rule, vec![&rule.record_type])),
+            }
+        }
+        let rules: Vec<Value> = groups
+            .into_iter()
+            .map(|(rule, types)| {
+                if rule.deny {
+                    json!({ "📄": types, "⛔": true })
+                } else {
+                    json!({
+                        "📄": types,
+                        "🥐": rule.append_roles,
+                        "🤝☝️": rule.quorum_k,
+                        "🤝🥐": rule.quorum_roles,
+                        "↔️": rule.rate_per_mark,
+                    })
+                }
+            })
+            .collect();
+
+        let mut m = Map::new();
+        let mut put = |k: &str, v: Value| {
+            if !v.is_null() {
+                m.insert(k.to_string(), v);
+            }
+        };
+        if !self.scope.is_empty() {
+            put("🌐", json!(self.scope));
+        }
+        put("🧱", json!(self.defaults));
+        put("⛓️", Value::Array(rules));
+        put("🤝⏳", json!(self.quorum_ttl));
+        put("🟢🕑", json!(self.effective_micromark));
+        put("🔴🕑", json!(self.expiration_micromark));
+        put("🗒️", json!(self.note));
+        put("📏📦", json!(self.max_record_bytes));
+        put("📏📊", json!(self.max_data_bytes));
+        if !self.inherit {
+            put("⬆️", json!(false));
+        }
+        Value::Object(m)
+    }
+}
+
+impl Serialize for Policy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_canonical().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Policy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Policy::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grouped() -> Value {
+        json!({
+            "schema": "rhex://schema/policy_set@0",
+            "🤝⏳": 1_000_000_000u64,
+            "d": { "🥐": ["👑", "root"], "🤝☝️": 1, "↔️": 80 },
+            "⛓️": [
+                { "📄": ["policy:set", "📦:📝"] },
+                { "record_type": "request:rhex", "roles": ["*"], "↔️": 10 },
+                { "📄": "record:*", "⛔": true },
+            ]
+        })
+    }
+
+    #[test]
+    fn expands_groups_and_round_trips() {
+        let policy = Policy::parse(&grouped()).unwrap();
+        let types: Vec<&str> = policy
+            .rules
+            .iter()
+            .map(|r| r.record_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["policy:set", "record:text", "request:rhex", "record:*"]
+        );
+        assert_eq!(policy.rules[1].rate_per_mark, 80);
+        assert_eq!(policy.rules[2].append_roles, vec!["*"]);
+        assert_eq!(policy.rules[2].quorum_k, 1);
+        assert!(policy.rules[3].deny);
+
+        let canonical = policy.to_canonical();
+        assert_eq!(canonical["⛓️"].as_array().unwrap().len(), 3);
+        let again = Policy::parse(&canonical).unwrap();
+        assert_eq!(again.to_canonical(), canonical);
+        assert_eq!(serde_json::to_value(&again).unwrap(), canonical);
+    }
+
+    #[test]
+    fn reads_checked_in_policies() {
+        for text in [
+            include_str!("../../../policy_set.json"),
+            include_str!("../../../test.json"),
+        ] {
+            let value: Value = serde_json::from_str(text).unwrap();
+            let policy = Policy::parse(&value).unwrap();
+            assert!(policy.rules.len() >= 8);
+        }
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let err = Policy::parse(&json!({
+            "🤝⏳": "soon",
+            "color": "blue",
+            "⛓️": [
+                { "📄": ["record:text", "nope:nope:nope"] },
+                { "📄": ["📦:📝"], "🥐": ["👑"] },
+                7,
+            ]
+        }))
+        .unwrap_err();
+        let paths: Vec<&str> = err.problems.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "🤝⏳",
+                "color",
+                "⛓️[0].🥐",
+                "⛓️[0].📄[1]",
+                "⛓️[1].📄[0]",
+                "⛓️[2]"
+            ]
+        );
+    }
+}
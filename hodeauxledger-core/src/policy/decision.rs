@@ -0,0 +1,386 @@
+use std::fmt;
+
+use super::{policy::Policy, rule::Rule};
+use crate::key::{key::Key, revocation::RevokeReason};
+use crate::record::record_type::RecordType;
+use crate::time::gt::GtTime;
+use crate::to_base64;
+
+/// Why an append was turned down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DenyReason {
+    /// 📄 isn't a record type we know.
+    UnknownRecordType(String),
+    /// No rule covers the 📄 and the policy has no 🧱 defaults.
+    NoRule {
+        record_type: String,
+    },
+    /// Author key was never granted in the scope.
+    KeyNotGranted,
+    /// Key grant isn't in force yet.
+    KeyNotYetEffective {
+        effective: u64,
+        at: u64,
+    },
+    /// Key grant ran out.
+    KeyExpired {
+        expires: u64,
+        at: u64,
+    },
+    /// Key was revoked by a key:revoke in force by then.
+    KeyRevoked {
+        reason: RevokeReason,
+        revoked: u64,
+        at: u64,
+    },
+    /// None of the key's 🥐 may append this 📄.
+    RoleNotAllowed {
+        record_type: String,
+        roles: Vec<String>,
+        allowed: Vec<String>,
+    },
+    /// A ⛔ rule forbids the 📄 here.
+    RuleDenies {
+        record_type: String,
+        rule: String,
+    },
+    PolicyNotYetEffective {
+        effective: u64,
+        at: u64,
+    },
+    PolicyExpired {
+        expires: u64,
+        at: u64,
+    },
+    /// The 🌐, or one above it, was closed by a scope:seal.
+    ScopeSealed {
+        /// The sealed 🌐, which may be a parent of the one appended to.
+        scope: String,
+        /// ⬇️🧬 of the scope:seal.
+        seal: [u8; 32],
+    },
+}
+
+/// Outcome of checking an append against a scope policy, with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppendDecision {
+    Allow {
+        /// 📄 of the rule that allowed it, or "🧱" for the defaults.
+        rule: String,
+        /// 🥐 that matched ("*" for open rules).
+        role: String,
+    },
+    Deny(DenyReason),
+}
+
+impl AppendDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, AppendDecision::Allow { .. })
+    }
+}
+
+/// Windows use 0 (or nothing) for "no end".
+fn expired(expires: Option<u64>, at: u64) -> Option<u64> {
+    expires.filter(|&x| x != 0 && at >= x)
+}
+
+fn not_yet(effective: Option<u64>, at: u64) -> Option<u64> {
+    effective.filter(|&e| e > at)
+}
+
+impl Policy {
+    /// The rule governing `record_type`, most specific first: exact 📄,
+    /// then `family:*`, then `*`. Deny rules are left to
+    /// [`Policy::denied_by`].
+    pub fn rule_for(&self, record_type: &RecordType) -> Option<&Rule> {
+        let specificity = |pattern: &str| match pattern {
+            "*" => 2,
+            p if p.ends_with(":*") => 1,
+            _ => 0,
+        };
+        self.rules
+            .iter()
+            .filter(|r| !r.deny && r.matches(record_type))
+            .min_by_key(|r| {
+                RecordType::parse(&r.record_type).map_or(3, |p| specificity(p.as_str()))
+            })
+    }
+
+    /// The first ⛔ rule covering `record_type`, if any.
+    pub fn denied_by(&self, record_type: &RecordType) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.deny && r.matches(record_type))
+    }
+
+    /// 📄 of the rule (or "🧱") governing `record_type` and the 🥐 it
+    /// allows, ignoring ⛔ rules.
+    fn allowed_roles(&self, record_type: &RecordType) -> Option<(String, &Vec<String>)> {
+        match (self.rule_for(record_type), &self.defaults) {
+            (Some(rule), _) => Some((rule.record_type.clone(), &rule.append_roles)),
+            (None, Some(defaults)) => Some(("🧱".to_string(), &defaults.roles)),
+            (None, None) => None,
+        }
+    }
+
+    /// Can anyone append `record_type`, granted or not?
+    pub fn is_open(&self, record_type: &RecordType) -> bool {
+        self.denied_by(record_type).is_none()
+            && self
+                .allowed_roles(record_type)
+                .is_some_and(|(_, roles)| roles.iter().any(|r| r == "*"))
+    }
+
+    /// May `key` append a `record_type` record at micromark `at`?
+    /// `key` is the author's cached grant in the scope, `None` if it has
+    /// none. A ⛔ rule always wins; otherwise uses [`Policy::rule_for`],
+    /// falling back to 🧱 defaults.
+    pub fn evaluate_append(&self, record_type: &str, key: Option<&Key>, at: u64) -> AppendDecision {
+        use DenyReason::*;
+        let deny = AppendDecision::Deny;
+
+        if let Some(effective) = not_yet(self.effective_micromark, at) {
+            return deny(PolicyNotYetEffective { effective, at });
+        }
+        if let Some(expires) = expired(self.expiration_micromark, at) {
+            return deny(PolicyExpired { expires, at });
+        }
+
+        let Ok(rt) = RecordType::parse(record_type) else {
+            return deny(UnknownRecordType(record_type.to_string()));
+        };
+
+        if let Some(rule) = self.denied_by(&rt) {
+            return deny(RuleDenies {
+                record_type: rt.to_string(),
+                rule: rule.record_type.clone(),
+            });
+        }
+
+        let Some((rule_name, allowed)) = self.allowed_roles(&rt) else {
+            return deny(NoRule {
+                record_type: rt.to_string(),
+            });
+        };
+
+        if allowed.iter().any(|r| r == "*") {
+            return AppendDecision::Allow {
+                rule: rule_name,
+                role: "*".to_string(),
+            };
+        }
+
+        let Some(key) = key else {
+            return deny(KeyNotGranted);
+        };
+        if let Some(effective) = not_yet(key.effective_micromark, at) {
+            return deny(KeyNotYetEffective { effective, at });
+        }
+        if let Some(expires) = expired(key.expires_micromark, at) {
+            return deny(KeyExpired { expires, at });
+        }
+        if let Some(revocation) = key.revoked.as_ref().filter(|r| r.in_force(at)) {
+            return deny(KeyRevoked {
+                reason: revocation.reason,
+                revoked: revocation.effective_micromark,
+                at,
+            });
+        }
+
+        let roles = key.roles.clone().unwrap_or_default();
+        match roles.iter().find(|r| allowed.contains(r)) {
+            Some(role) => AppendDecision::Allow {
+                rule: rule_name,
+                role: role.clone(),
+            },
+            None => deny(RoleNotAllowed {
+                record_type: rt.to_string(),
+                roles,
+                allowed: allowed.clone(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for DenyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DenyReason::UnknownRecordType(rt) => write!(f, "unknown 📄 {rt}"),
+            DenyReason::NoRule { record_type } => {
+                write!(f, "no rule for 📄 {record_type} and no 🧱 defaults")
+            }
+            DenyReason::KeyNotGranted => write!(f, "author 🔑 is not granted in this 🌐"),
+            DenyReason::KeyNotYetEffective { effective, at } => {
+                write!(
+                    f,
+                    "author 🔑 not effective until {} (at {})",
+                    GtTime::from(*effective),
+                    GtTime::from(*at)
+                )
+            }
+            DenyReason::KeyExpired { expires, at } => {
+                write!(
+                    f,
+                    "author 🔑 expired at {} (at {})",
+                    GtTime::from(*expires),
+                    GtTime::from(*at)
+                )
+            }
+            DenyReason::KeyRevoked {
+                reason,
+                revoked,
+                at,
+            } => {
+                write!(
+                    f,
+                    "author 🔑 revoked ({reason}) at {} (at {})",
+                    GtTime::from(*revoked),
+                    GtTime::from(*at)
+                )
+            }
+            DenyReason::RoleNotAllowed {
+                record_type,
+                roles,
+                allowed,
+            } => write!(
+                f,
+                "🥐 [{}] may not append 📄 {record_type}, needs one of [{}]",
+                roles.join(","),
+                allowed.join(",")
+            ),
+            DenyReason::RuleDenies { record_type, rule } => {
+                write!(f, "📄 {record_type} is denied by ⛔ rule {rule}")
+            }
+            DenyReason::PolicyNotYetEffective { effective, at } => {
+                write!(
+                    f,
+                    "📜 not effective until {} (at {})",
+                    GtTime::from(*effective),
+                    GtTime::from(*at)
+                )
+            }
+            DenyReason::PolicyExpired { expires, at } => {
+                write!(
+                    f,
+                    "📜 expired at {} (at {})",
+                    GtTime::from(*expires),
+                    GtTime::from(*at)
+                )
+            }
+            DenyReason::ScopeSealed { scope, seal } => {
+                write!(f, "🌐:{scope} is sealed by {}", to_base64(seal))
+            }
+        }
+    }
+}
+
+impl fmt::Display for AppendDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppendDecision::Allow { rule, role } => {
+                write!(f, "allowed by rule {rule} as 🥐 {role}")
+            }
+            AppendDecision::Deny(reason) => write!(f, "denied: {reason}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::revocation::Revocation;
+    use crate::policy::default::Default;
+
+    fn policy() -> Policy {
+        let mut policy = Policy::new(
+            "core",
+            vec![
+                Rule::new("record:*", &["writer"], 1, 10),
+                Rule::new("record:text", &["👑"], 1, 10),
+                Rule::new("request:rhex", &["*"], 1, 10),
+            ],
+        );
+        policy.defaults = Some(Default {
+            roles: vec!["👑".to_string()],
+            quorum_k: 1,
+            quorum_roles: vec![],
+            rate_per_mark: 10,
+        });
+        policy.expiration_micromark = Some(1000);
+        policy
+    }
+
+    fn key(roles: &[&str]) -> Key {
+        let mut key = Key::from_bytes(&[3u8; 32]);
+        key.roles = Some(roles.iter().map(|r| r.to_string()).collect());
+        key.effective_micromark = Some(10);
+        key.expires_micromark = Some(500);
+        key
+    }
+
+    #[test]
+    fn most_specific_rule_wins() {
+        let p = policy();
+        let writer = key(&["writer"]);
+        assert!(p.evaluate_append("📦:🖼️", Some(&writer), 100).is_allowed());
+        assert!(matches!(
+            p.evaluate_append("record:text", Some(&writer), 100),
+            AppendDecision::Deny(DenyReason::RoleNotAllowed { .. })
+        ));
+        assert_eq!(
+            p.evaluate_append("record:text", Some(&key(&["👑"])), 100),
+            AppendDecision::Allow {
+                rule: "record:text".to_string(),
+                role: "👑".to_string()
+            }
+        );
+        // Falls back to 🧱, and open rules need no grant.
+        assert!(
+            p.evaluate_append("key:grant", Some(&key(&["👑"])), 100)
+                .is_allowed()
+        );
+        assert!(p.evaluate_append("request:rhex", None, 100).is_allowed());
+        assert!(p.is_open(&RecordType::parse("request:rhex").unwrap()));
+        assert!(!p.is_open(&RecordType::parse("key:grant").unwrap()));
+        assert_eq!(
+            p.evaluate_append("key:grant", None, 100),
+            AppendDecision::Deny(DenyReason::KeyNotGranted)
+        );
+    }
+
+    #[test]
+    fn windows() {
+        let p = policy();
+        let k = key(&["writer"]);
+        assert!(matches!(
+            p.evaluate_append("record:data", Some(&k), 5),
+            AppendDecision::Deny(DenyReason::KeyNotYetEffective { effective: 10, .. })
+        ));
+        assert!(matches!(
+            p.evaluate_append("record:data", Some(&k), 600),
+            AppendDecision::Deny(DenyReason::KeyExpired { expires: 500, .. })
+        ));
+        assert!(matches!(
+            p.evaluate_append("request:rhex", None, 1000),
+            AppendDecision::Deny(DenyReason::PolicyExpired { expires: 1000, .. })
+        ));
+    }
+
+    #[test]
+    fn revoked_keys() {
+        let p = policy();
+        let mut k = key(&["writer"]);
+        k.revoked = Some(Revocation {
+            reason: RevokeReason::Compromised,
+            effective_micromark: 200,
+            compromised_micromark: Some(150),
+            revoke_ref: None,
+        });
+        assert!(p.evaluate_append("record:data", Some(&k), 199).is_allowed());
+        assert!(matches!(
+            p.evaluate_append("record:data", Some(&k), 200),
+            AppendDecision::Deny(DenyReason::KeyRevoked { revoked: 200, .. })
+        ));
+        let r = k.revoked.as_ref().unwrap();
+        assert!(!r.is_suspect(149));
+        assert!(r.is_suspect(150));
+    }
+}
@@ -6,7 +6,7 @@ pub struct Default {
     pub roles: Vec<String>,
     #[serde(rename = "🤝☝️", alias = "quorum_k")]
     pub quorum_k: u8,
-    #[serde(rename = "🤝🥐", alias = "quorum_roles")]
+    #[serde(rename = "🤝🥐", alias = "quorum_roles", default)]
     pub quorum_roles: Vec<String>,
     #[serde(rename = "↔️", alias = "rate_per_mark")]
     pub rate_per_mark: u64,
@@ -0,0 +1,267 @@
+use std::fmt;
+
+use super::{policy::Policy, rule::Rule};
+use crate::record::record_type::RecordType;
+use crate::time::gt::{GtDuration, GtTime};
+
+/// A scope and its parents, root first: `a.b.c` gives
+/// `["", "a", "a.b", "a.b.c"]`.
+pub fn lineage(scope: &str) -> Vec<String> {
+    let mut scopes = vec![String::new()];
+    if scope.is_empty() {
+        return scopes;
+    }
+    let mut name = String::new();
+    for part in scope.split('.') {
+        if !name.is_empty() {
+            name.push('.');
+        }
+        name.push_str(part);
+        scopes.push(name.clone());
+    }
+    scopes
+}
+
+/// A scope's 📜 after folding in everything it inherits.
+#[derive(Debug, Clone)]
+pub struct EffectivePolicy {
+    pub policy: Policy,
+    /// Scope each of `policy.rules` came from, same order.
+    pub sources: Vec<String>,
+    /// Scope the 🧱 defaults came from.
+    pub defaults_from: Option<String>,
+}
+
+impl EffectivePolicy {
+    /// Merge `policies`, root first and ending with the scope's own, into
+    /// the policy in force for `scope`:
+    ///
+    /// * a rule replaces an inherited rule for the same 📄;
+    /// * deny rules are never replaced or dropped, so a parent's ⛔ holds
+    ///   for every scope below it;
+    /// * 🧱, 🤝⏳ come from the nearest scope that sets them;
+    /// * size caps only ever tighten;
+    /// * a policy with ⬆️ off starts over, keeping only the denies;
+    /// * the 🟢🕑/🔴🕑 window and 🗒️ are the scope's own.
+    pub fn resolve<'a, I>(scope: &str, policies: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Policy>,
+    {
+        let mut merged = Policy::new(scope, Vec::new());
+        let mut sources: Vec<String> = Vec::new();
+        let mut defaults_from = None;
+
+        for p in policies {
+            if !p.inherit {
+                let mut i = 0;
+                while i < merged.rules.len() {
+                    if merged.rules[i].deny {
+                        i += 1;
+                    } else {
+                        merged.rules.remove(i);
+                        sources.remove(i);
+                    }
+                }
+                merged.defaults = None;
+                defaults_from = None;
+                merged.quorum_ttl = None;
+                merged.max_record_bytes = None;
+                merged.max_data_bytes = None;
+            }
+
+            for rule in &p.rules {
+                let key = canonical(&rule.record_type);
+                let existing = merged
+                    .rules
+                    .iter()
+                    .position(|r| r.deny == rule.deny && canonical(&r.record_type) == key);
+                match existing {
+                    Some(_) if rule.deny => {}
+                    Some(i) => {
+                        merged.rules[i] = rule.clone();
+                        sources[i] = p.scope.clone();
+                    }
+                    None => {
+                        merged.rules.push(rule.clone());
+                        sources.push(p.scope.clone());
+                    }
+                }
+            }
+
+            if p.defaults.is_some() {
+                merged.defaults = p.defaults.clone();
+                defaults_from = Some(p.scope.clone());
+            }
+            if p.quorum_ttl.is_some() {
+                merged.quorum_ttl = p.quorum_ttl;
+            }
+            merged.max_record_bytes = tighter(merged.max_record_bytes, p.max_record_bytes);
+            merged.max_data_bytes = tighter(merged.max_data_bytes, p.max_data_bytes);
+
+            if p.scope == scope {
+                merged.effective_micromark = p.effective_micromark;
+                merged.expiration_micromark = p.expiration_micromark;
+                merged.note = p.note.clone();
+                merged.inherit = p.inherit;
+            }
+        }
+
+        Self {
+            policy: merged,
+            sources,
+            defaults_from,
+        }
+    }
+
+    /// Rules in force, each with the scope it came from.
+    pub fn rules(&self) -> impl Iterator<Item = (&Rule, &str)> {
+        self.policy
+            .rules
+            .iter()
+            .zip(self.sources.iter().map(String::as_str))
+    }
+}
+
+fn canonical(record_type: &str) -> String {
+    RecordType::parse(record_type).map_or_else(|_| record_type.to_string(), |rt| rt.to_string())
+}
+
+fn tighter(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn scope_label(scope: &str) -> &str {
+    if scope.is_empty() { "(root)" } else { scope }
+}
+
+impl fmt::Display for EffectivePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = &self.policy;
+        writeln!(f, "🌐 {} effective 📜", scope_label(&p.scope))?;
+        match (&p.defaults, &self.defaults_from) {
+            (Some(d), from) => writeln!(
+                f,
+                "  🧱 🥐 [{}] 🤝☝️ {} ↔️ {}  ← {}",
+                d.roles.join(","),
+                d.quorum_k,
+                d.rate_per_mark,
+                from.as_deref().map_or("built-in", scope_label)
+            )?,
+            (None, _) => writeln!(f, "  🧱 none")?,
+        }
+        for (rule, from) in self.rules() {
+            if rule.deny {
+                writeln!(f, "  ⛔ {}  ← {}", rule.record_type, scope_label(from))?;
+            } else {
+                writeln!(
+                    f,
+                    "  ⛓️ {} 🥐 [{}] 🤝☝️ {} ↔️ {}  ← {}",
+                    rule.record_type,
+                    rule.append_roles.join(","),
+                    rule.quorum_k,
+                    rule.rate_per_mark,
+                    scope_label(from)
+                )?;
+            }
+        }
+        match (p.effective_micromark, p.expiration_micromark) {
+            (None | Some(0), None | Some(0)) => {}
+            (eff, exp) => writeln!(
+                f,
+                "  🟢🕑 {}  🔴🕑 {}",
+                GtTime::from(eff.unwrap_or(0)),
+                exp.filter(|&e| e != 0)
+                    .map_or("never".to_string(), |e| GtTime::from(e).to_string())
+            )?,
+        }
+        if let Some(ttl) = p.quorum_ttl {
+            writeln!(f, "  🤝⏳ {}", GtDuration::from_micromarks(ttl))?;
+        }
+        if let Some(n) = p.max_record_bytes {
+            writeln!(f, "  📏📦 {n}")?;
+        }
+        if let Some(n) = p.max_data_bytes {
+            writeln!(f, "  📏📊 {n}")?;
+        }
+        if !p.inherit {
+            writeln!(f, "  ⬆️ off")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::decision::{AppendDecision, DenyReason};
+    use crate::policy::default::Default;
+
+    fn policy(scope: &str, rules: Vec<Rule>) -> Policy {
+        Policy::new(scope, rules)
+    }
+
+    #[test]
+    fn lineage_is_root_first() {
+        assert_eq!(lineage(""), vec![""]);
+        assert_eq!(lineage("a.b.c"), vec!["", "a", "a.b", "a.b.c"]);
+    }
+
+    #[test]
+    fn child_overrides_and_denies_stick() {
+        let mut root = policy(
+            "",
+            vec![
+                Rule::new("policy:set", &["👑"], 1, 80),
+                Rule::new("record:text", &["writer"], 1, 10),
+                Rule::deny("record:*"),
+            ],
+        );
+        root.defaults = Some(Default {
+            roles: vec!["👑".to_string()],
+            quorum_k: 1,
+            quorum_roles: vec![],
+            rate_per_mark: 5,
+        });
+        root.max_data_bytes = Some(512);
+        let mid = policy("a", vec![Rule::new("📦:📝", &["editor"], 1, 20)]);
+        let mut leaf = policy("a.b", vec![Rule::new("record:*", &["*"], 1, 1)]);
+        leaf.max_data_bytes = Some(900);
+
+        let eff = EffectivePolicy::resolve("a.b", [&root, &mid, &leaf]);
+        let rules: Vec<(&str, &str)> = eff
+            .rules()
+            .map(|(r, from)| (r.record_type.as_str(), from))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("policy:set", ""),
+                ("📦:📝", "a"),
+                ("record:*", ""),
+                ("record:*", "a.b"),
+            ]
+        );
+        assert_eq!(eff.defaults_from.as_deref(), Some(""));
+        assert_eq!(eff.policy.max_data_bytes, Some(512));
+
+        let p = &eff.policy;
+        assert_eq!(
+            p.evaluate_append("record:text", None, 0),
+            AppendDecision::Deny(DenyReason::RuleDenies {
+                record_type: "record:text".to_string(),
+                rule: "record:*".to_string(),
+            })
+        );
+
+        // Cutting inheritance drops rules and defaults but not the ⛔.
+        let mut cut = policy("a.b", vec![]);
+        cut.inherit = false;
+        let eff = EffectivePolicy::resolve("a.b", [&root, &mid, &cut]);
+        assert!(eff.policy.defaults.is_none());
+        assert_eq!(eff.policy.rules.len(), 1);
+        assert!(eff.policy.rules[0].deny);
+    }
+}
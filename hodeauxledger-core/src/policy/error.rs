@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// One thing wrong with a 📜, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyProblem {
+    /// Where in the document, e.g. `⛓️[2].📄[0]`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Every problem found while reading a 📜, not just the first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyError {
+    pub problems: Vec<PolicyProblem>,
+}
+
+impl PolicyError {
+    pub fn push(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.problems.push(PolicyProblem {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for PolicyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bad 📜, {} problem(s)", self.problems.len())?;
+        for (i, problem) in self.problems.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{sep}{problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PolicyError {}
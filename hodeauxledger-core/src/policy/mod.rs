@@ -1,3 +1,8 @@
+pub mod canonical;
+pub mod decision;
 pub mod default;
+pub mod effective;
+pub mod error;
 pub mod policy;
+pub mod rate;
 pub mod rule;
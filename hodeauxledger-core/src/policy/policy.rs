@@ -1,23 +1,20 @@
-use serde::{Deserialize, Serialize};
+use crate::policy::{default::Default, error::PolicyError, rule::Rule};
 
-use crate::policy::{default::Default, rule::Rule};
-
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// A scope's append rules. Read and written through
+/// [`Policy::parse`] and [`Policy::to_canonical`]; the field comments give
+/// the canonical names.
+#[derive(Debug, Clone, Default)]
 pub struct Policy {
-    #[serde(rename = "🌐", alias = "scope")]
-    pub scope: String, // String name of the scope this affects
-    #[serde(rename = "🧱", alias = "defaults")]
-    pub defaults: Option<Default>, // Optional default policy
-    #[serde(rename = "⛓️", alias = "rules")]
-    pub rules: Vec<Rule>, // Set of append rules
-    #[serde(rename = "🤝⏳", alias = "quorum_ttl")]
-    pub quorum_ttl: Option<u64>, // Amount of time to give quorum
-    #[serde(rename = "🟢🕑", alias = "effective_micromark")]
-    pub effective_micromark: Option<u64>, // Effective micromark time
-    #[serde(rename = "🔴🕑", alias = "expiration_micromark")]
-    pub expiration_micromark: Option<u64>, // Expiration micromark time
-    #[serde(rename = "🗒️", alias = "note")]
-    pub note: Option<String>, // Optional note
+    pub scope: String,                     // 🌐 String name of the scope this affects
+    pub defaults: Option<Default>,         // 🧱 Optional default policy
+    pub rules: Vec<Rule>,                  // ⛓️ Set of append rules
+    pub quorum_ttl: Option<u64>,           // 🤝⏳ Amount of time to give quorum
+    pub effective_micromark: Option<u64>,  // 🟢🕑 Effective micromark time
+    pub expiration_micromark: Option<u64>, // 🔴🕑 Expiration micromark time
+    pub note: Option<String>,              // 🗒️ Optional note
+    pub max_record_bytes: Option<usize>,   // 📏📦 Tighter cap on whole R⬢ size
+    pub max_data_bytes: Option<usize>,     // 📏📊 Tighter cap on 📊 size
+    pub inherit: bool,                     // ⬆️ Take parent scopes' rules and defaults
 }
 
 impl Policy {
@@ -30,6 +27,9 @@ impl Policy {
             effective_micromark: None,
             expiration_micromark: None,
             note: None,
+            max_record_bytes: None,
+            max_data_bytes: None,
+            inherit: true,
         }
     }
 
@@ -48,22 +48,18 @@ impl Policy {
             effective_micromark: Some(0),
             expiration_micromark: Some(0),
             note: None,
+            max_record_bytes: None,
+            max_data_bytes: None,
+            inherit: true,
         }
     }
 
+    /// Same as [`Policy::to_canonical`].
     pub fn to_json(&self) -> serde_json::Value {
-        serde_json::json!({
-            "⛓️": self.rules,
-            "🧱": self.defaults,
-            "🗒️": self.note,
-            "🌐": self.scope,
-            "🤝⏳": self.quorum_ttl,
-            "🟢🕑": self.effective_micromark,
-            "🔴🕑": self.expiration_micromark,
-        })
+        self.to_canonical()
     }
 
-    pub fn from_json(json: serde_json::Value) -> Self {
-        serde_json::from_value(json).expect("failed to parse policy")
+    pub fn from_json(json: serde_json::Value) -> Result<Self, PolicyError> {
+        Self::parse(&json)
     }
 }
@@ -0,0 +1,96 @@
+use super::policy::Policy;
+use crate::record::record_type::RecordType;
+
+pub use crate::time::time::MICROMARKS_PER_MARK;
+
+/// Appends counted so far in one mark-long window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RateState {
+    /// Mark the window covers (`at / MICROMARKS_PER_MARK`).
+    pub mark: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    /// Go ahead; `remaining` more fit in this mark.
+    Allow { remaining: u64 },
+    /// Over ↔️ for this mark, try again at micromark `retry_at`.
+    Limited { retry_at: u64 },
+}
+
+impl RateDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, RateDecision::Allow { .. })
+    }
+}
+
+/// Count one append at micromark `at` against ↔️ `rate_per_mark`. Returns
+/// the decision and the state to store; a limited append isn't counted.
+/// A rate of 0 means no limit.
+pub fn take(state: Option<RateState>, rate_per_mark: u64, at: u64) -> (RateDecision, RateState) {
+    let mark = at / MICROMARKS_PER_MARK;
+    let mut state = match state {
+        Some(s) if s.mark == mark => s,
+        _ => RateState { mark, count: 0 },
+    };
+    if rate_per_mark == 0 {
+        return (
+            RateDecision::Allow {
+                remaining: u64::MAX,
+            },
+            state,
+        );
+    }
+    if state.count >= rate_per_mark {
+        return (
+            RateDecision::Limited {
+                retry_at: (mark + 1) * MICROMARKS_PER_MARK,
+            },
+            state,
+        );
+    }
+    state.count += 1;
+    (
+        RateDecision::Allow {
+            remaining: rate_per_mark - state.count,
+        },
+        state,
+    )
+}
+
+impl Policy {
+    /// ↔️ in force for `record_type`: the most specific matching rule's,
+    /// else the 🧱 defaults'. `None` when neither applies.
+    pub fn rate_per_mark(&self, record_type: &RecordType) -> Option<u64> {
+        match self.rule_for(record_type) {
+            Some(rule) => Some(rule.rate_per_mark),
+            None => self.defaults.as_ref().map(|d| d.rate_per_mark),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_then_waits_for_next_mark() {
+        let at = 5 * MICROMARKS_PER_MARK + 10;
+        let (d, s) = take(None, 2, at);
+        assert_eq!(d, RateDecision::Allow { remaining: 1 });
+        let (d, s) = take(Some(s), 2, at + 1);
+        assert_eq!(d, RateDecision::Allow { remaining: 0 });
+        let (d, s) = take(Some(s), 2, at + 2);
+        assert_eq!(
+            d,
+            RateDecision::Limited {
+                retry_at: 6 * MICROMARKS_PER_MARK
+            }
+        );
+        assert_eq!(s.count, 2);
+        let (d, s) = take(Some(s), 2, 6 * MICROMARKS_PER_MARK);
+        assert!(d.is_allowed());
+        assert_eq!(s, RateState { mark: 6, count: 1 });
+    }
+}
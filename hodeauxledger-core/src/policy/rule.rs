@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+use crate::record::record_type::RecordType;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Rule {
+    #[serde(rename = "📄", alias = "record_type")]
     pub record_type: String,
+    #[serde(rename = "🥐", alias = "append_roles", alias = "roles")]
     pub append_roles: Vec<String>,
+    #[serde(rename = "🤝☝️", alias = "quorum_k")]
     pub quorum_k: u8,
+    #[serde(rename = "🤝🥐", alias = "quorum_roles", default)]
     pub quorum_roles: Vec<String>,
+    #[serde(rename = "↔️", alias = "rate_per_mark")]
     pub rate_per_mark: u64,
+    /// Forbid the 📄 outright, in this scope and below, whatever a
+    /// parent's rules say.
+    #[serde(rename = "⛔", alias = "deny", default)]
+    pub deny: bool,
 }
 
 impl Rule {
@@ -18,6 +29,25 @@ impl Rule {
             quorum_k,
             quorum_roles: vec![],
             rate_per_mark,
+            deny: false,
+        }
+    }
+
+    /// Does this rule cover `record_type`? The rule's 📄 may be a pattern
+    /// like `record:*`, and either side may be in emoji form.
+    pub fn matches(&self, record_type: &RecordType) -> bool {
+        RecordType::parse(&self.record_type).is_ok_and(|p| p.matches(record_type))
+    }
+
+    /// A rule that forbids `record_type` for everyone.
+    pub fn deny(record_type: &str) -> Self {
+        Self {
+            record_type: record_type.to_string(),
+            append_roles: vec![],
+            quorum_k: 0,
+            quorum_roles: vec![],
+            rate_per_mark: 0,
+            deny: true,
         }
     }
 
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use super::body::Payload;
+use crate::{crypto::b64::b64_32, scope::alias::Alias};
+
+/// 🅰️:🟢 alias:grant — see docs/schema/alias_grant@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AliasGrant {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    pub name: String,
+    /// ⬇️🧬 the alias points at.
+    #[serde(rename = "🎯", alias = "t", alias = "target", with = "b64_32")]
+    pub target: [u8; 32],
+}
+
+/// 🅰️:🔴 alias:revoke — see docs/schema/alias_revoke@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AliasRevoke {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    pub name: String,
+}
+
+impl AliasGrant {
+    pub fn to_alias(&self, scope: &str) -> Alias {
+        Alias::new(&self.name, scope, &self.target)
+    }
+}
+
+impl Payload for AliasGrant {}
+impl Payload for AliasRevoke {}
@@ -0,0 +1,95 @@
+use anyhow::{Result, bail};
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+
+use super::body::Payload;
+use crate::crypto::b64::b64_32;
+use crate::scope::authority::Authority;
+
+/// 👑:🟢 authority:grant — see docs/schema/authority_grant@0.md. Also the
+/// shape of each 👑 entry in scope:request / scope:create.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorityGrant {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    pub name: String,
+    #[serde(alias = "h")]
+    pub host: String,
+    #[serde(alias = "p")]
+    pub port: u16,
+    #[serde(alias = "pro", default, skip_serializing_if = "Option::is_none")]
+    pub proto: Option<String>,
+    #[serde(alias = "pri", default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(rename = "🔓", alias = "pk", alias = "public_key", with = "b64_32")]
+    pub public_key: [u8; 32],
+}
+
+/// 👑:🔴 authority:revoke — see docs/schema/authority_revoke@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorityRevoke {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    #[serde(rename = "🔓", alias = "pk", alias = "public_key", with = "b64_32")]
+    pub public_key: [u8; 32],
+}
+
+impl AuthorityGrant {
+    /// Checks beyond the field types in docs/schema/authority_grant@0.md.
+    /// Reports every problem at once.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        if self.name.trim().is_empty() {
+            problems.push("name is empty".to_string());
+        }
+        if self.host.trim().is_empty() {
+            problems.push("host is empty".to_string());
+        }
+        if self.port == 0 {
+            problems.push("port is 0".to_string());
+        }
+        if self.proto.as_ref().is_some_and(|p| p.trim().is_empty()) {
+            problems.push("proto is empty".to_string());
+        }
+        if let Some(priority) = self.priority.filter(|&p| p > 100) {
+            problems.push(format!("priority {priority} is over 100"));
+        }
+        if VerifyingKey::from_bytes(&self.public_key).is_err() {
+            problems.push("🔓 is not an ed25519 public key".to_string());
+        }
+        if !problems.is_empty() {
+            bail!("bad 👑: {}", problems.join("; "));
+        }
+        Ok(())
+    }
+
+    pub fn to_authority(&self) -> Authority {
+        Authority {
+            name: self.name.clone(),
+            host: self.host.clone(),
+            port: self.port,
+            proto: self.proto.clone().unwrap_or_else(|| "rhex".to_string()),
+            public_key: self.public_key,
+            priority: self.priority.unwrap_or(50),
+        }
+    }
+}
+
+impl Payload for AuthorityGrant {}
+impl Payload for AuthorityRevoke {}
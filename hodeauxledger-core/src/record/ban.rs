@@ -0,0 +1,37 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::{body::Payload, record_type::RecordType};
+use crate::crypto::b64::b64_32;
+
+/// 📦:🚫 record:ban — see docs/schema/record_ban@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordBan {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    /// ⬇️🧬 of the record to withhold.
+    #[serde(rename = "🎯", alias = "t", alias = "target", with = "b64_32")]
+    pub target: [u8; 32],
+}
+
+impl RecordBan {
+    /// Only 📦 content can be banned. Scope, key, policy and other records
+    /// are needed to follow the chain, and a ban can't be banned.
+    pub fn check_target_type(record_type: &str) -> Result<()> {
+        let rt = RecordType::parse(record_type)?;
+        if rt.family() != "record" || rt.as_str() == "record:ban" {
+            bail!("📄 {record_type} can't be banned");
+        }
+        Ok(())
+    }
+}
+
+impl Payload for RecordBan {}
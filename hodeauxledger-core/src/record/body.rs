@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use super::{
+    alias::{AliasGrant, AliasRevoke},
+    authority::{AuthorityGrant, AuthorityRevoke},
+    ban::RecordBan,
+    key::{KeyGrant, KeyRevoke},
+    policy::PolicySet,
+    record_type::RecordType,
+    request::{RequestRhex, RequestScope},
+    scope::{ScopeCreate, ScopeGenesis, ScopeRequest, ScopeSeal},
+};
+use crate::rhex::rhex::Rhex;
+
+/// A typed 📊. Field names follow docs/schema: records are written with the
+/// emoji (or long) name and read with the short, long or emoji one.
+pub trait Payload: Serialize + DeserializeOwned {
+    fn from_data(data: &Value) -> Result<Self> {
+        Ok(serde_json::from_value(data.clone())?)
+    }
+
+    fn to_data(&self) -> Result<Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+}
+
+/// 📊 of a R⬢, parsed according to its 📄.
+#[derive(Debug, Clone)]
+pub enum RecordBody {
+    ScopeGenesis(ScopeGenesis),
+    ScopeRequest(ScopeRequest),
+    ScopeCreate(ScopeCreate),
+    ScopeSeal(ScopeSeal),
+    PolicySet(PolicySet),
+    KeyGrant(KeyGrant),
+    KeyRevoke(KeyRevoke),
+    AuthorityGrant(AuthorityGrant),
+    AuthorityRevoke(AuthorityRevoke),
+    AliasGrant(AliasGrant),
+    AliasRevoke(AliasRevoke),
+    RecordBan(RecordBan),
+    RequestRhex(RequestRhex),
+    RequestHead(RequestScope),
+    RequestPolicy(RequestScope),
+    RequestAlias(RequestScope),
+    /// Record types without a typed body (📦 records, steward, ...).
+    Other(Value),
+}
+
+impl RecordBody {
+    pub fn parse(record_type: &str, data: &Value) -> Result<Self> {
+        fn typed<T: Payload>(data: &Value, f: fn(T) -> RecordBody) -> Result<RecordBody> {
+            T::from_data(data).map(f)
+        }
+        let Ok(rt) = RecordType::parse(record_type) else {
+            return Ok(RecordBody::Other(data.clone()));
+        };
+        let body = match rt.as_str() {
+            "scope:genesis" => typed(data, RecordBody::ScopeGenesis),
+            "scope:request" => typed(data, RecordBody::ScopeRequest),
+            "scope:create" => typed(data, RecordBody::ScopeCreate),
+            "scope:seal" => typed(data, RecordBody::ScopeSeal),
+            "policy:set" => typed(data, RecordBody::PolicySet),
+            "key:grant" => typed(data, RecordBody::KeyGrant),
+            "key:revoke" => typed(data, RecordBody::KeyRevoke),
+            "authority:grant" => typed(data, RecordBody::AuthorityGrant),
+            "authority:revoke" => typed(data, RecordBody::AuthorityRevoke),
+            "alias:grant" => typed(data, RecordBody::AliasGrant),
+            "alias:revoke" => typed(data, RecordBody::AliasRevoke),
+            "record:ban" => typed(data, RecordBody::RecordBan),
+            "request:rhex" => typed(data, RecordBody::RequestRhex),
+            "request:head" => typed(data, RecordBody::RequestHead),
+            "request:policy" => typed(data, RecordBody::RequestPolicy),
+            "request:alias" => typed(data, RecordBody::RequestAlias),
+            _ => Ok(RecordBody::Other(data.clone())),
+        };
+        body.with_context(|| format!("bad 📊 for 📄 {record_type}"))
+    }
+
+    pub fn from_rhex(rhex: &Rhex) -> Result<Self> {
+        Self::parse(&rhex.intent.record_type, &rhex.intent.data)
+    }
+
+    /// Back to `intent.data`, in canonical field names.
+    pub fn to_data(&self) -> Result<Value> {
+        match self {
+            RecordBody::ScopeGenesis(b) => b.to_data(),
+            RecordBody::ScopeRequest(b) => b.to_data(),
+            RecordBody::ScopeCreate(b) => b.to_data(),
+            RecordBody::ScopeSeal(b) => b.to_data(),
+            RecordBody::PolicySet(b) => b.to_data(),
+            RecordBody::KeyGrant(b) => b.to_data(),
+            RecordBody::KeyRevoke(b) => b.to_data(),
+            RecordBody::AuthorityGrant(b) => b.to_data(),
+            RecordBody::AuthorityRevoke(b) => b.to_data(),
+            RecordBody::AliasGrant(b) => b.to_data(),
+            RecordBody::AliasRevoke(b) => b.to_data(),
+            RecordBody::RecordBan(b) => b.to_data(),
+            RecordBody::RequestRhex(b) => b.to_data(),
+            RecordBody::RequestHead(b)
+            | RecordBody::RequestPolicy(b)
+            | RecordBody::RequestAlias(b) => b.to_data(),
+            RecordBody::Other(v) => Ok(v.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_base64;
+    use serde_json::json;
+
+    #[test]
+    fn genesis_accepts_legacy_epoch_field() {
+        let data = json!({
+            "schema": "rhex://schema/scope_genesis@0",
+            "description": "root",
+            "unix_at": 1756704877985u64,
+        });
+        match RecordBody::parse("scope:genesis", &data).unwrap() {
+            RecordBody::ScopeGenesis(g) => {
+                assert_eq!(g.unix_epoch_ms, Some(1756704877985));
+                assert_eq!(g.note.as_deref(), Some("root"));
+                assert_eq!(
+                    g.to_data().unwrap(),
+                    json!({
+                        "schema": "rhex://schema/scope_genesis@0",
+                        "🗒️": "root",
+                        "unix_epoch_ms": 1756704877985u64,
+                    })
+                );
+            }
+            other => panic!("wrong body {other:?}"),
+        }
+    }
+
+    #[test]
+    fn short_long_and_emoji_names_agree() {
+        let pk = to_base64(&[9u8; 32]);
+        let short = json!({ "pk": pk, "r": ["👑"], "eff": 1, "exp": 2 });
+        let long = json!({
+            "public_key": pk, "roles": ["👑"],
+            "effective_micromark": 1, "expires_micromark": 2,
+        });
+        let emoji = json!({ "🔓": pk, "🥐": ["👑"], "🟢🕑": 1, "🔴🕑": 2 });
+
+        let parse = |v| match RecordBody::parse("🔑:🟢", &v).unwrap() {
+            RecordBody::KeyGrant(g) => g,
+            other => panic!("wrong body {other:?}"),
+        };
+        let grant = parse(short);
+        assert_eq!(grant, parse(long));
+        assert_eq!(grant.public_key, [9u8; 32]);
+        assert_eq!(grant.to_data().unwrap(), emoji.clone());
+        assert_eq!(grant, parse(emoji));
+    }
+
+    #[test]
+    fn missing_required_field_is_an_error() {
+        let err = RecordBody::parse("scope:create", &json!({ "a": [] })).unwrap_err();
+        assert!(format!("{err:#}").contains("new_scope"));
+        assert!(matches!(
+            RecordBody::parse("record:text", &json!({ "text": "hi" })).unwrap(),
+            RecordBody::Other(_)
+        ));
+    }
+}
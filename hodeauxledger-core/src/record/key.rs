@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::body::Payload;
+use crate::crypto::b64::{b64_32, b64_32_opt};
+use crate::key::revocation::{Revocation, RevokeReason};
+
+/// 🔑:🟢 key:grant — see docs/schema/key_grant@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyGrant {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    #[serde(rename = "🔓", alias = "pk", alias = "public_key", with = "b64_32")]
+    pub public_key: [u8; 32],
+    #[serde(rename = "🥐", alias = "r", alias = "roles")]
+    pub roles: Vec<String>,
+    #[serde(rename = "🟢🕑", alias = "eff", alias = "effective_micromark")]
+    pub effective_micromark: u64,
+    #[serde(rename = "🔴🕑", alias = "exp", alias = "expires_micromark")]
+    pub expires_micromark: u64,
+}
+
+/// 🔑:🔴 key:revoke — see docs/schema/key_revoke@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyRevoke {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    #[serde(rename = "🔓", alias = "pk", alias = "public_key", with = "b64_32")]
+    pub public_key: [u8; 32],
+    /// ⬇️🧬 of the key:grant being revoked.
+    #[serde(
+        alias = "gr",
+        with = "b64_32_opt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub grant_ref: Option<[u8; 32]>,
+    #[serde(rename = "🟢🕑", alias = "eff", alias = "effective_micromark")]
+    pub effective_micromark: u64,
+    #[serde(rename = "❓", alias = "rsn", alias = "reason")]
+    pub reason: RevokeReason,
+    /// For compromised keys: records signed from here on are suspect.
+    /// Defaults to 🟢🕑.
+    #[serde(
+        rename = "☠️🕑",
+        alias = "cmp",
+        alias = "compromised_micromark",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub compromised_micromark: Option<u64>,
+}
+
+impl KeyRevoke {
+    /// What this revoke, appended as `revoke_ref`, does to the key.
+    pub fn revocation(&self, revoke_ref: Option<[u8; 32]>) -> Revocation {
+        Revocation {
+            reason: self.reason,
+            effective_micromark: self.effective_micromark,
+            compromised_micromark: self.compromised_micromark,
+            revoke_ref,
+        }
+    }
+}
+
+impl Payload for KeyGrant {}
+impl Payload for KeyRevoke {}
@@ -0,0 +1,9 @@
+pub mod alias;
+pub mod authority;
+pub mod ban;
+pub mod body;
+pub mod key;
+pub mod policy;
+pub mod record_type;
+pub mod request;
+pub mod scope;
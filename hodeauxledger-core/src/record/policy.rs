@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::body::Payload;
+use crate::policy::policy::Policy;
+
+/// 📜:🟢 policy:set — see docs/schema/policy_set@0.md
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicySet {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(flatten)]
+    pub policy: Policy,
+}
+
+impl Payload for PolicySet {}
@@ -0,0 +1,307 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
+use anyhow::{Result, anyhow, bail};
+
+/// Record families: (canonical, emoji).
+const FAMILIES: &[(&str, Option<&str>)] = &[
+    ("scope", Some("🌐")),
+    ("policy", Some("📜")),
+    ("key", Some("🔑")),
+    ("authority", Some("👑")),
+    ("alias", Some("🅰️")),
+    ("record", Some("📦")),
+    ("request", Some("📩")),
+    ("steward", Some("💩")),
+    ("response", None),
+    ("confirm", None),
+    ("error", None),
+];
+
+/// Everything in docs/RECORD-TYPES.md plus the protocol replies:
+/// (canonical, emoji).
+const BUILTIN: &[(&str, Option<&str>)] = &[
+    ("scope:genesis", Some("🌐:💡")),
+    ("scope:request", Some("🌐:📩")),
+    ("scope:create", Some("🌐:🟢")),
+    ("scope:seal", Some("🌐:🔴")),
+    ("policy:set", Some("📜:🟢")),
+    ("key:grant", Some("🔑:🟢")),
+    ("key:revoke", Some("🔑:🔴")),
+    ("authority:grant", Some("👑:🟢")),
+    ("authority:revoke", Some("👑:🔴")),
+    ("alias:grant", Some("🅰️:🟢")),
+    ("alias:revoke", Some("🅰️:🔴")),
+    ("record:data", Some("📦:📊")),
+    ("record:text", Some("📦:📝")),
+    ("record:image", Some("📦:🖼️")),
+    ("record:audio", Some("📦:🎵")),
+    ("record:video", Some("📦:🎥")),
+    ("record:document", Some("📦:📄")),
+    ("record:link", Some("📦:🔗")),
+    ("record:package", Some("📦:📦")),
+    ("record:piece", Some("📦:🧩")),
+    ("record:ban", Some("📦:🚫")),
+    ("request:rhex", Some("📩:R⬢")),
+    ("request:head", Some("📩:➡️🧬")),
+    ("request:policy", Some("📩:📜")),
+    ("request:alias", Some("📩:🅰️")),
+    ("steward:info", Some("💩:🔷")),
+    ("steward:error", Some("💩:❌")),
+    ("steward:warning", Some("💩:⚠️")),
+    ("response:head", None),
+    ("confirm:ok", None),
+    ("error:verify_failed", None),
+    ("error:append_denied", None),
+    ("error:rate_limited", None),
+    ("error:request_failed", None),
+    ("error:time_rejected", None),
+];
+
+/// (canonical, emoji) of a registered type.
+type Entry = (String, Option<String>);
+
+/// Types registered at runtime by downstream crates.
+fn custom() -> &'static RwLock<Vec<Entry>> {
+    static CUSTOM: OnceLock<RwLock<Vec<Entry>>> = OnceLock::new();
+    CUSTOM.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Find a registered type by either form, giving back both.
+fn lookup(s: &str) -> Option<Entry> {
+    if let Some((c, e)) = BUILTIN.iter().find(|(c, e)| *c == s || *e == Some(s)) {
+        return Some((c.to_string(), e.map(str::to_string)));
+    }
+    let custom = custom().read().unwrap();
+    custom
+        .iter()
+        .find(|(c, e)| c == s || e.as_deref() == Some(s))
+        .cloned()
+}
+
+/// Canonical family name for either form.
+fn lookup_family(s: &str) -> Option<String> {
+    if let Some((c, _)) = FAMILIES.iter().find(|(c, e)| *c == s || *e == Some(s)) {
+        return Some(c.to_string());
+    }
+    let custom = custom().read().unwrap();
+    custom.iter().find_map(|(c, e)| {
+        let family = c.split_once(':')?.0;
+        let emoji = e.as_deref().and_then(|e| e.split_once(':')).map(|e| e.0);
+        (family == s || emoji == Some(s)).then(|| family.to_string())
+    })
+}
+
+fn family_emoji(family: &str) -> Option<String> {
+    if let Some((_, e)) = FAMILIES.iter().find(|(c, _)| *c == family) {
+        return e.map(str::to_string);
+    }
+    let custom = custom().read().unwrap();
+    custom.iter().find_map(|(c, e)| {
+        (c.split_once(':')?.0 == family)
+            .then(|| e.as_deref()?.split_once(':').map(|e| e.0.to_string()))
+            .flatten()
+    })
+}
+
+/// A 📄, held in its canonical ASCII form (`scope:genesis`) whichever form
+/// it was written in. Can also be a pattern: `*` or `record:*`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RecordType {
+    canonical: String,
+}
+
+impl RecordType {
+    /// Parse either form (`🌐:💡` or `scope:genesis`), a wildcard, or a
+    /// generic `request:<family>_<name>`. Unknown types are an error.
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s == "*" {
+            return Ok(Self::new(s));
+        }
+        if let Some((canonical, _)) = lookup(s) {
+            return Ok(Self::new(&canonical));
+        }
+        let (family, name) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("📄 {s} has no family"))?;
+        let family = lookup_family(family).ok_or_else(|| anyhow!("unknown 📄 family {family}"))?;
+        if name == "*" {
+            return Ok(Self::new(&format!("{family}:*")));
+        }
+        // request:record_text asks for every record:text
+        if family == "request"
+            && let Some((f, n)) = name.split_once('_')
+            && let Ok(inner) = Self::parse(&format!("{f}:{n}"))
+            && !inner.is_wildcard()
+        {
+            return Ok(Self::new(&format!(
+                "request:{}",
+                inner.canonical.replacen(':', "_", 1)
+            )));
+        }
+        bail!("unknown 📄 {s}")
+    }
+
+    fn new(canonical: &str) -> Self {
+        Self {
+            canonical: canonical.to_string(),
+        }
+    }
+
+    /// Register a custom type, e.g. `("invoice:issue", Some("🧾:🟢"))`.
+    /// Registering the same pair again is fine, clashing with an existing
+    /// type is not.
+    pub fn register(canonical: &str, emoji: Option<&str>) -> Result<Self> {
+        let (family, name) = canonical
+            .split_once(':')
+            .ok_or_else(|| anyhow!("📄 {canonical} has no family"))?;
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        };
+        if !valid(family) || !valid(name) || family.contains('_') {
+            bail!("📄 {canonical} must be family:name in lowercase ASCII");
+        }
+        if let Some(e) = emoji
+            && !e.contains(':')
+        {
+            bail!("emoji 📄 {e} has no family");
+        }
+
+        if let Some(existing) = lookup(canonical) {
+            if existing.1.as_deref() == emoji {
+                return Ok(Self::new(canonical));
+            }
+            bail!("📄 {canonical} is already registered");
+        }
+        if let Some(e) = emoji
+            && lookup(e).is_some()
+        {
+            bail!("emoji 📄 {e} is already registered");
+        }
+
+        custom()
+            .write()
+            .unwrap()
+            .push((canonical.to_string(), emoji.map(str::to_string)));
+        Ok(Self::new(canonical))
+    }
+
+    /// Every concrete type we know about, built-ins first.
+    pub fn known() -> Vec<RecordType> {
+        let mut out: Vec<RecordType> = BUILTIN.iter().map(|(c, _)| Self::new(c)).collect();
+        out.extend(custom().read().unwrap().iter().map(|(c, _)| Self::new(c)));
+        out
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.canonical
+    }
+
+    /// The emoji form, if there is one.
+    pub fn emoji(&self) -> Option<String> {
+        if let Some((_, e)) = lookup(&self.canonical) {
+            return e;
+        }
+        let (family, name) = self.canonical.split_once(':')?;
+        (name == "*")
+            .then(|| family_emoji(family).map(|f| format!("{f}:*")))
+            .flatten()
+    }
+
+    pub fn family(&self) -> &str {
+        self.canonical
+            .split_once(':')
+            .map_or(&self.canonical, |(f, _)| f)
+    }
+
+    pub fn is_wildcard(&self) -> bool {
+        self.canonical == "*" || self.canonical.ends_with(":*")
+    }
+
+    /// Does `record_type` fall under this type or pattern?
+    pub fn matches(&self, record_type: &RecordType) -> bool {
+        if self.canonical == "*" {
+            return true;
+        }
+        if self.canonical.ends_with(":*") {
+            return self.family() == record_type.family();
+        }
+        self == record_type
+    }
+
+    /// [`RecordType::matches`] straight from strings. Anything that doesn't
+    /// parse matches nothing.
+    pub fn matches_str(pattern: &str, record_type: &str) -> bool {
+        match (Self::parse(pattern), Self::parse(record_type)) {
+            (Ok(p), Ok(rt)) => p.matches(&rt),
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for RecordType {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.canonical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_forms_normalise() {
+        for (c, e) in BUILTIN {
+            assert_eq!(RecordType::parse(c).unwrap().as_str(), *c);
+            if let Some(e) = e {
+                let rt = RecordType::parse(e).unwrap();
+                assert_eq!(rt.as_str(), *c);
+                assert_eq!(rt.emoji().as_deref(), Some(*e));
+            }
+        }
+        assert!(RecordType::parse("scope:nope").is_err());
+        assert!(RecordType::parse("request").is_err());
+    }
+
+    #[test]
+    fn wildcards_and_generic_requests() {
+        let text = RecordType::parse("📦:📝").unwrap();
+        assert!(RecordType::parse("record:*").unwrap().matches(&text));
+        assert!(RecordType::parse("📦:*").unwrap().matches(&text));
+        assert!(RecordType::parse("*").unwrap().matches(&text));
+        assert!(!RecordType::parse("scope:*").unwrap().matches(&text));
+        assert_eq!(
+            RecordType::parse("📦:*").unwrap().emoji().as_deref(),
+            Some("📦:*")
+        );
+
+        let req = RecordType::parse("request:record_text").unwrap();
+        assert_eq!(req.as_str(), "request:record_text");
+        assert_eq!(RecordType::parse("📩:📦_📝").unwrap(), req);
+        assert!(RecordType::matches_str("request:*", "request:record_text"));
+    }
+
+    #[test]
+    fn custom_types() {
+        let rt = RecordType::register("invoice:issue", Some("🧾:🟢")).unwrap();
+        assert_eq!(RecordType::parse("🧾:🟢").unwrap(), rt);
+        assert!(RecordType::matches_str("🧾:*", "invoice:issue"));
+        assert!(RecordType::known().contains(&rt));
+        RecordType::register("invoice:issue", Some("🧾:🟢")).unwrap();
+        assert!(RecordType::register("invoice:issue", None).is_err());
+        assert!(RecordType::register("invoice:void", Some("🌐:💡")).is_err());
+        assert!(RecordType::register("Bad Type", None).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::body::Payload;
+use crate::crypto::b64::b64_32_opt;
+
+/// 📩:R⬢ request:rhex — see docs/schema/request_rhex@0.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestRhex {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    /// 📄 filter, empty means everything.
+    #[serde(
+        rename = "📄📄",
+        alias = "rt",
+        alias = "record_types",
+        alias = "types",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub record_types: Vec<String>,
+    #[serde(alias = "mr", default, skip_serializing_if = "Option::is_none")]
+    pub max_return: Option<u64>,
+    /// ⬇️🧬 to start after, genesis if missing.
+    #[serde(
+        alias = "s",
+        with = "b64_32_opt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start: Option<[u8; 32]>,
+}
+
+/// 📊 of requests that only name a scope (request:head, request:policy,
+/// request:alias) — see docs/schema/request_head@0.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestScope {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+}
+
+impl Payload for RequestRhex {}
+impl Payload for RequestScope {}
@@ -0,0 +1,138 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::{authority::AuthorityGrant, body::Payload};
+use crate::scope::name::ScopeName;
+
+/// 🌐:💡 scope:genesis — see docs/schema/scope_genesis@0.md
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScopeGenesis {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        alias = "description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    /// Only on the root genesis: the Unix time GT counts from.
+    #[serde(
+        alias = "UTC",
+        alias = "unix_at",
+        alias = "unix_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unix_epoch_ms: Option<u64>,
+    /// Authorities the scope starts out with, granted 👑. Without them the
+    /// genesis author runs the scope.
+    #[serde(
+        rename = "👑",
+        alias = "a",
+        alias = "authorities",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub authorities: Vec<AuthorityGrant>,
+}
+
+/// 🌐:📩 scope:request — see docs/schema/scope_request@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopeRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    #[serde(alias = "ns")]
+    pub new_scope: String,
+    #[serde(rename = "👑", alias = "a", alias = "authorities")]
+    pub authorities: Vec<AuthorityGrant>,
+    /// 📊 for the child's 🌐:💡.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis: Option<ScopeGenesis>,
+}
+
+/// 🌐:🟢 scope:create — see docs/schema/scope_create@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopeCreate {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    #[serde(alias = "ns")]
+    pub new_scope: String,
+    #[serde(rename = "👑", alias = "a", alias = "authorities")]
+    pub authorities: Vec<AuthorityGrant>,
+}
+
+/// 🌐:🔴 scope:seal — see docs/schema/scope_seal@0.md
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopeSeal {
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "sch")]
+    pub schema: Option<String>,
+    #[serde(
+        rename = "🗒️",
+        alias = "n",
+        alias = "note",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub note: Option<String>,
+    /// Child scope being sealed.
+    #[serde(alias = "ss")]
+    pub sealed_scope: String,
+}
+
+impl ScopeRequest {
+    /// Checks a request sent to `parent` beyond the field types in
+    /// docs/schema/scope_request@0.md. Reports every problem at once.
+    pub fn validate(&self, parent: &str) -> Result<()> {
+        let mut problems = Vec::new();
+        match ScopeName::parse(&self.new_scope) {
+            Err(e) => problems.push(e.to_string()),
+            Ok(name) if name.parent().is_none_or(|p| p.as_str() != parent) => problems.push(
+                format!("🌐:{} isn't a child of 🌐:{parent}", self.new_scope),
+            ),
+            Ok(_) => {}
+        }
+        if self.authorities.is_empty() {
+            problems.push("no 👑 given".to_string());
+        }
+        for (i, authority) in self.authorities.iter().enumerate() {
+            if let Err(e) = authority.validate() {
+                problems.push(format!("👑[{i}]: {e}"));
+            }
+        }
+        match &self.genesis {
+            None => problems.push("missing genesis".to_string()),
+            Some(g) if g.unix_epoch_ms.is_some() => {
+                problems.push("only the root genesis sets unix_epoch_ms".to_string())
+            }
+            Some(_) => {}
+        }
+        if !problems.is_empty() {
+            bail!("bad 🌐:📩: {}", problems.join("; "));
+        }
+        Ok(())
+    }
+}
+
+impl Payload for ScopeGenesis {}
+impl Payload for ScopeRequest {}
+impl Payload for ScopeCreate {}
+impl Payload for ScopeSeal {}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use ed25519_dalek::{Signature as DalekSig, VerifyingKey};
+
+use super::{
+    error::RhexError,
+    rhex::Rhex,
+    signature::{SigType, Signature},
+};
+
+/// Signatures handed to ed25519-dalek per batch. A failed batch is
+/// re-checked one signature at a time, so smaller batches make a bad
+/// record cheaper to find.
+pub const BATCH_SIZE: usize = 1024;
+
+/// One signature a record has to satisfy.
+#[derive(Debug, Clone)]
+pub struct SigCheck {
+    pub role: SigType,
+    /// Position in the record's 🖊️🖊️🖊️.
+    pub index: usize,
+    pub key: VerifyingKey,
+    pub prehash: [u8; 32],
+    pub sig: DalekSig,
+}
+
+impl SigCheck {
+    /// Refuses low-order keys, and an 🖊️ whose R is low order or not in
+    /// canonical form. The batch equation can't tell those apart from good
+    /// signatures, so they're turned away before either path sees them.
+    pub fn new(
+        sig: &Signature,
+        prehash: [u8; 32],
+        role: SigType,
+        index: usize,
+    ) -> Result<Self, RhexError> {
+        let key = VerifyingKey::from_bytes(&sig.public_key)
+            .map_err(|_| RhexError::InvalidPublicKey { role, index })?;
+        if key.is_weak() {
+            return Err(RhexError::InvalidPublicKey { role, index });
+        }
+        let r = CompressedEdwardsY::from_slice(&sig.sig[..32])
+            .map_err(|_| RhexError::InvalidSignature { role, index })?;
+        match r.decompress() {
+            Some(point) if !point.is_small_order() && point.compress() == r => {}
+            _ => return Err(RhexError::InvalidSignature { role, index }),
+        }
+        Ok(Self {
+            role,
+            index,
+            key,
+            prehash,
+            sig: DalekSig::from_bytes(&sig.sig),
+        })
+    }
+
+    /// Checks this one signature on its own, strictly.
+    pub fn verify(&self) -> Result<(), RhexError> {
+        self.key
+            .verify_strict(&self.prehash, &self.sig)
+            .map_err(|_| RhexError::InvalidSignature {
+                role: self.role,
+                index: self.index,
+            })
+    }
+}
+
+/// Collects signature checks across many records and verifies them with
+/// ed25519-dalek's batch verification.
+///
+/// [`SigCheck::new`] filters out what the batch and [`SigCheck::verify`]
+/// would judge differently, so both give the same answer for anything an
+/// honest signer makes. One difference is left: an R with a small torsion
+/// component added fails on its own but can pass in a batch. Only the key's
+/// holder can make such a signature, and ruling it out would cost a scalar
+/// multiplication per signature.
+#[derive(Debug, Default)]
+pub struct BatchVerifier {
+    checks: Vec<(usize, SigCheck)>,
+}
+
+impl BatchVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue every signature of `rhex`, tagged with `record` so failures
+    /// can be traced back to it.
+    pub fn push(&mut self, record: usize, rhex: &Rhex) -> Result<(), RhexError> {
+        let checks = if rhex.is_tombstone() {
+            rhex.tombstone_signature_checks()?
+        } else {
+            rhex.signature_checks()?
+        };
+        for check in checks {
+            self.checks.push((record, check));
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Verify everything queued. Returns the first bad signature of each
+    /// failing record, in record order; empty means all good.
+    pub fn verify(self) -> Vec<(usize, RhexError)> {
+        let mut failed: BTreeMap<usize, RhexError> = BTreeMap::new();
+        for chunk in self.checks.chunks(BATCH_SIZE) {
+            let msgs: Vec<&[u8]> = chunk.iter().map(|(_, c)| &c.prehash[..]).collect();
+            let sigs: Vec<DalekSig> = chunk.iter().map(|(_, c)| c.sig).collect();
+            let keys: Vec<VerifyingKey> = chunk.iter().map(|(_, c)| c.key).collect();
+            if ed25519_dalek::verify_batch(&msgs, &sigs, &keys).is_ok() {
+                continue;
+            }
+            // Something in here is bad, go find out what.
+            for (record, check) in chunk {
+                if failed.contains_key(record) {
+                    continue;
+                }
+                if let Err(e) = check.verify() {
+                    failed.insert(*record, e);
+                }
+            }
+        }
+        failed.into_iter().collect()
+    }
+}
+
+/// [`Rhex::validate`] for a slice of records, with the signatures checked
+/// in batches. Returns the first problem of each bad record.
+pub fn validate_all(records: &[Rhex]) -> Vec<(usize, RhexError)> {
+    let mut errors = Vec::new();
+    let mut batch = BatchVerifier::new();
+    for (i, rhex) in records.iter().enumerate() {
+        if let Err(e) = rhex.validate_structure().and_then(|_| batch.push(i, rhex)) {
+            errors.push((i, e));
+        }
+    }
+    errors.extend(batch.verify());
+    errors.sort_by_key(|(i, _)| *i);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intent, Key};
+
+    fn signed(nonce: &str) -> Rhex {
+        let author = Key::from_bytes(&[7u8; 32]);
+        let mut rhex = Rhex::draft(Intent::new(
+            &[0u8; 32],
+            "core",
+            nonce,
+            &author.to_bytes(),
+            &[0u8; 32],
+            "record:text",
+            serde_json::json!({ "text": nonce }),
+        ));
+        let pre = rhex.author_prehash().unwrap();
+        rhex.signatures.push(Signature {
+            sig_type: 0,
+            public_key: author.to_bytes(),
+            sig: author.sign(&pre).unwrap().to_bytes(),
+        });
+        rhex
+    }
+
+    /// Author-signs `rhex` again with R = identity and s = H(R‖A‖M)·a,
+    /// which plain ed25519 verification accepts.
+    fn small_order_r(mut rhex: Rhex) -> Rhex {
+        use curve25519_dalek::scalar::Scalar;
+        use ed25519_dalek::SigningKey;
+        use sha2::{Digest, Sha512};
+
+        let sk = SigningKey::from_bytes(&[7u8; 32]);
+        let pre = rhex.author_prehash().unwrap();
+        let mut r = [0u8; 32];
+        r[0] = 1;
+        let mut h = Sha512::new();
+        h.update(r);
+        h.update(sk.verifying_key().as_bytes());
+        h.update(pre);
+        let h = Scalar::from_bytes_mod_order_wide(&h.finalize().into());
+        let sig = &mut rhex.signatures[0].sig;
+        sig[..32].copy_from_slice(&r);
+        sig[32..].copy_from_slice((h * sk.to_scalar()).as_bytes());
+        rhex
+    }
+
+    #[test]
+    fn batch_and_single_agree() {
+        let mut weak = signed("weak");
+        // The identity as a key, with R = identity and s = 0: "valid" for
+        // any message.
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        weak.intent.author_public_key = identity;
+        weak.signatures[0].public_key = identity;
+        weak.signatures[0].sig = [0u8; 64];
+        weak.signatures[0].sig[0] = 1;
+
+        let mut tampered = signed("tampered");
+        tampered.signatures[0].sig[40] ^= 0x01;
+
+        let cases = [
+            (signed("good"), true),
+            (tampered, false),
+            (weak, false),
+            (small_order_r(signed("small")), false),
+        ];
+        let filler: Vec<Rhex> = (0..8).map(|i| signed(&format!("f{i}"))).collect();
+        for (rhex, good) in cases {
+            assert_eq!(rhex.validate().is_ok(), good, "{}", rhex.intent.nonce);
+            assert_eq!(validate_all(std::slice::from_ref(&rhex)).is_empty(), good);
+            let mut records = filler.clone();
+            records.push(rhex);
+            let bad: Vec<usize> = validate_all(&records).iter().map(|(i, _)| *i).collect();
+            assert_eq!(bad.is_empty(), good);
+            assert!(bad.iter().all(|&i| i == 8));
+        }
+    }
+
+    #[test]
+    fn finds_the_bad_signature_in_a_batch() {
+        let mut records: Vec<Rhex> = (0..40).map(|i| signed(&format!("n{i}"))).collect();
+        assert!(validate_all(&records).is_empty());
+
+        records[17].signatures[0].sig[5] ^= 0x01;
+        records[33].intent.nonce = "swapped".into();
+        let errors = validate_all(&records);
+        let bad: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(bad, vec![17, 33]);
+        assert!(matches!(
+            errors[0].1,
+            RhexError::InvalidSignature {
+                role: SigType::Author,
+                index: 0
+            }
+        ));
+    }
+}
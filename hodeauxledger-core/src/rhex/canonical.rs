@@ -0,0 +1,287 @@
+//! Deterministic CBOR encoding (RFC 8949 §4.2.1 "core deterministic").
+//!
+//! Every hash and signature in the ledger is computed over bytes produced
+//! here, so any implementation that follows the same rules gets the same
+//! bytes:
+//!
+//! * integers, lengths and tags use the shortest possible head
+//! * floats use the shortest of f16/f32/f64 that keeps the exact value
+//!   (NaN is always `f9 7e00`)
+//! * map entries are sorted by the bytewise order of their encoded keys
+//! * no indefinite-length items
+//!
+//! See docs/CANONICAL-CBOR.md for the published test vectors.
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use serde_cbor::Value;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+/// Serialize any value into canonical CBOR bytes.
+pub fn to_canonical_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let v = serde_cbor::value::to_value(value)?;
+    let mut out = Vec::new();
+    encode_value(&v, &mut out)?;
+    Ok(out)
+}
+
+/// Encode an already built CBOR value tree into `out`.
+pub fn encode_value(value: &Value, out: &mut Vec<u8>) -> Result<()> {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Integer(i) => encode_integer(*i, out)?,
+        Value::Float(f) => encode_float(*f, out),
+        Value::Bytes(b) => {
+            write_head(MAJOR_BYTES, b.len() as u64, out);
+            out.extend_from_slice(b);
+        }
+        Value::Text(s) => {
+            write_head(MAJOR_TEXT, s.len() as u64, out);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(MAJOR_ARRAY, items.len() as u64, out);
+            for item in items {
+                encode_value(item, out)?;
+            }
+        }
+        Value::Map(map) => {
+            let mut entries = Vec::with_capacity(map.len());
+            for (k, v) in map {
+                let mut key = Vec::new();
+                encode_value(k, &mut key)?;
+                entries.push((key, v));
+            }
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            if entries.windows(2).any(|w| w[0].0 == w[1].0) {
+                bail!("duplicate map key in canonical CBOR");
+            }
+            write_head(MAJOR_MAP, entries.len() as u64, out);
+            for (key, v) in entries {
+                out.extend_from_slice(&key);
+                encode_value(v, out)?;
+            }
+        }
+        Value::Tag(tag, inner) => {
+            write_head(MAJOR_TAG, *tag, out);
+            encode_value(inner, out)?;
+        }
+        _ => bail!("unsupported CBOR value"),
+    }
+    Ok(())
+}
+
+fn encode_integer(i: i128, out: &mut Vec<u8>) -> Result<()> {
+    if i >= 0 {
+        let n = u64::try_from(i).map_err(|_| anyhow::anyhow!("integer {i} out of CBOR range"))?;
+        write_head(MAJOR_UNSIGNED, n, out);
+    } else {
+        let n =
+            u64::try_from(-1 - i).map_err(|_| anyhow::anyhow!("integer {i} out of CBOR range"))?;
+        write_head(MAJOR_NEGATIVE, n, out);
+    }
+    Ok(())
+}
+
+/// Major type + argument using the shortest head that fits.
+fn write_head(major: u8, n: u64, out: &mut Vec<u8>) {
+    let mt = major << 5;
+    if n < 24 {
+        out.push(mt | n as u8);
+    } else if n <= u8::MAX as u64 {
+        out.push(mt | 24);
+        out.push(n as u8);
+    } else if n <= u16::MAX as u64 {
+        out.push(mt | 25);
+        out.extend_from_slice(&(n as u16).to_be_bytes());
+    } else if n <= u32::MAX as u64 {
+        out.push(mt | 26);
+        out.extend_from_slice(&(n as u32).to_be_bytes());
+    } else {
+        out.push(mt | 27);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn encode_float(f: f64, out: &mut Vec<u8>) {
+    if f.is_nan() {
+        out.extend_from_slice(&[0xf9, 0x7e, 0x00]);
+        return;
+    }
+    let single = f as f32;
+    if single as f64 == f {
+        if let Some(half) = f32_to_f16_exact(single) {
+            out.push(0xf9);
+            out.extend_from_slice(&half.to_be_bytes());
+        } else {
+            out.push(0xfa);
+            out.extend_from_slice(&single.to_bits().to_be_bytes());
+        }
+    } else {
+        out.push(0xfb);
+        out.extend_from_slice(&f.to_bits().to_be_bytes());
+    }
+}
+
+/// Returns the IEEE 754 half-precision bits for `x` if (and only if) the
+/// conversion is lossless.
+fn f32_to_f16_exact(x: f32) -> Option<u16> {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x007f_ffff;
+
+    if exp == 0xff {
+        // Infinity (NaN is handled by the caller)
+        return if mant == 0 { Some(sign | 0x7c00) } else { None };
+    }
+    if exp == 0 {
+        // ±0 fits; f32 subnormals are far below the f16 range
+        return if mant == 0 { Some(sign) } else { None };
+    }
+
+    let e = exp - 127;
+    if e > 15 {
+        return None;
+    }
+    if e >= -14 {
+        // Normal half: the low 13 mantissa bits must be zero
+        if mant & 0x1fff != 0 {
+            return None;
+        }
+        return Some(sign | (((e + 15) as u16) << 10) | (mant >> 13) as u16);
+    }
+
+    // Subnormal half: value = m * 2^-24 with m in 1..=1023
+    let significand = mant | 0x0080_0000;
+    let shift = -(e + 1);
+    if shift > 24 {
+        return None;
+    }
+    if significand & ((1u32 << shift) - 1) != 0 {
+        return None;
+    }
+    Some(sign | (significand >> shift) as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intent, Key, Rhex, Signature};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn enc(v: Value) -> String {
+        let mut out = Vec::new();
+        encode_value(&v, &mut out).unwrap();
+        hex(&out)
+    }
+
+    #[test]
+    fn rfc8949_appendix_a_vectors() {
+        assert_eq!(enc(Value::Integer(0)), "00");
+        assert_eq!(enc(Value::Integer(23)), "17");
+        assert_eq!(enc(Value::Integer(24)), "1818");
+        assert_eq!(enc(Value::Integer(1000)), "1903e8");
+        assert_eq!(enc(Value::Integer(1_000_000)), "1a000f4240");
+        assert_eq!(enc(Value::Integer(1_000_000_000_000)), "1b000000e8d4a51000");
+        assert_eq!(enc(Value::Integer(u64::MAX as i128)), "1bffffffffffffffff");
+        assert_eq!(enc(Value::Integer(-1)), "20");
+        assert_eq!(enc(Value::Integer(-100)), "3863");
+        assert_eq!(enc(Value::Integer(-1000)), "3903e7");
+        assert_eq!(enc(Value::Float(0.0)), "f90000");
+        assert_eq!(enc(Value::Float(-0.0)), "f98000");
+        assert_eq!(enc(Value::Float(1.0)), "f93c00");
+        assert_eq!(enc(Value::Float(1.5)), "f93e00");
+        assert_eq!(enc(Value::Float(65504.0)), "f97bff");
+        assert_eq!(enc(Value::Float(100000.0)), "fa47c35000");
+        assert_eq!(enc(Value::Float(5.960464477539063e-8)), "f90001");
+        assert_eq!(enc(Value::Float(0.00006103515625)), "f90400");
+        assert_eq!(enc(Value::Float(1.1)), "fb3ff199999999999a");
+        assert_eq!(enc(Value::Float(f64::INFINITY)), "f97c00");
+        assert_eq!(enc(Value::Float(f64::NEG_INFINITY)), "f9fc00");
+        assert_eq!(enc(Value::Float(f64::NAN)), "f97e00");
+        assert_eq!(enc(Value::Text("IETF".into())), "6449455446");
+        assert_eq!(enc(Value::Bytes(vec![1, 2, 3, 4])), "4401020304");
+    }
+
+    #[test]
+    fn map_keys_sorted_bytewise() {
+        let json = serde_json::json!({ "aa": 3, "b": 2, "a": 1, "🌐": 4 });
+        let bytes = to_canonical_cbor(&json).unwrap();
+        assert_eq!(hex(&bytes), "a46161016162026261610364f09f8c9004");
+    }
+
+    fn vector_intent() -> Intent {
+        let author = Key::from_bytes(&[1u8; 32]);
+        let usher = Key::from_bytes(&[2u8; 32]);
+        Intent::new(
+            &[0u8; 32],
+            "core",
+            "AAAAAAAAAAAAAAAA",
+            &author.to_bytes(),
+            &usher.to_bytes(),
+            "record:text",
+            serde_json::json!({ "text": "hello", "n": 1, "f": 1.5, "neg": -1 }),
+        )
+    }
+
+    fn vector_record() -> Rhex {
+        let author = Key::from_bytes(&[1u8; 32]);
+        let usher = Key::from_bytes(&[2u8; 32]);
+        let mut rhex = Rhex::draft(vector_intent());
+        rhex.magic = *b"RHEX\x00\x00";
+        rhex.context.at = 1000;
+        let pre = rhex.author_prehash().unwrap();
+        let author_sig = author.sign(&pre).unwrap().to_bytes();
+        rhex.signatures.push(Signature {
+            sig_type: 0,
+            public_key: author.to_bytes(),
+            sig: author_sig,
+        });
+        let pre = rhex.usher_prehash(&author_sig).unwrap();
+        rhex.signatures.push(Signature {
+            sig_type: 1,
+            public_key: usher.to_bytes(),
+            sig: usher.sign(&pre).unwrap().to_bytes(),
+        });
+        rhex.finalize().unwrap()
+    }
+
+    const INTENT_CBOR: &str = "a764f09f8c9064636f726564f09f8eb2704141414141414141414141414141414164f09f93846b7265636f72643a7465787464f09f938aa46166f93e00616e01636e65672064746578746568656c6c6f68f09f93a3f09f949358208139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b3946ae29c8defb88ff09f949358208a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c6ae2ac85efb88ff09fa7ac58200000000000000000000000000000000000000000000000000000000000000000";
+    const SIGNATURES_CBOR: &str = "82a364f09f949358208a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c64f09fa4980067f09f968aefb88f5840cf08f196853183ee8d461b72e980f2279bae0308baa5828760ac683c960bb792a20a59efdd04a6058098c043175b77da905c06a8967e7cc1ac80a4c200dd780fa364f09f949358208139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b39464f09fa4980167f09f968aefb88f58400e2f69a7e3faea902cbce4d537a12ebd8cd057e3926650efd47149548f341cadf5ba6d2370a7d4fee195a225eea62cf5ec67293acfa17e87456fa0b39c91ea0c";
+    const RECORD_CBOR: &str = "a564f09f8eafa764f09f8c9064636f726564f09f8eb2704141414141414141414141414141414164f09f93846b7265636f72643a7465787464f09f938aa46166f93e00616e01636e65672064746578746568656c6c6f68f09f93a3f09f949358208139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b3946ae29c8defb88ff09f949358208a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c6ae2ac85efb88ff09fa7ac5820000000000000000000000000000000000000000000000000000000000000000064f09faa844652484558000067f09f96bcefb88fa166e28fb1efb88f1903e86ae2ac87efb88ff09fa7ac5820d25d3162105d65de8049a1a372244477d23549e08bf8c25949f7409d58a2454075f09f968aefb88ff09f968aefb88ff09f968aefb88f82a364f09f949358208a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c64f09fa4980067f09f968aefb88f5840cf08f196853183ee8d461b72e980f2279bae0308baa5828760ac683c960bb792a20a59efdd04a6058098c043175b77da905c06a8967e7cc1ac80a4c200dd780fa364f09f949358208139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b39464f09fa4980167f09f968aefb88f58400e2f69a7e3faea902cbce4d537a12ebd8cd057e3926650efd47149548f341cadf5ba6d2370a7d4fee195a225eea62cf5ec67293acfa17e87456fa0b39c91ea0c";
+    const RECORD_HASH: &str = "d25d3162105d65de8049a1a372244477d23549e08bf8c25949f7409d58a24540";
+
+    #[test]
+    fn published_intent_vector() {
+        let bytes = to_canonical_cbor(&vector_intent()).unwrap();
+        assert_eq!(hex(&bytes), INTENT_CBOR);
+    }
+
+    #[test]
+    fn published_signature_vector() {
+        let rhex = vector_record();
+        let bytes = to_canonical_cbor(&rhex.signatures).unwrap();
+        assert_eq!(hex(&bytes), SIGNATURES_CBOR);
+    }
+
+    #[test]
+    fn published_record_vector() {
+        let rhex = vector_record();
+        assert_eq!(hex(&rhex.pack().unwrap()), RECORD_CBOR);
+        assert_eq!(hex(&rhex.current_hash().unwrap()), RECORD_HASH);
+        rhex.validate().unwrap();
+    }
+}
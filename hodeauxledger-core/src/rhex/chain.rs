@@ -0,0 +1,415 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{batch::BatchVerifier, error::RhexError, rhex::Rhex};
+use crate::crypto::b64::to_base64;
+use crate::record::body::RecordBody;
+use crate::time::gt::GtTime;
+
+/// One thing wrong with a chain.
+#[derive(Debug)]
+pub enum ChainIssueKind {
+    /// First record doesn't point at the zero hash.
+    GenesisNotZero { previous_hash: [u8; 32] },
+    /// ⬅️🧬 doesn't match the previous record's ⬇️🧬.
+    BrokenLink { expected: [u8; 32], found: [u8; 32] },
+    /// ⏱️ isn't after the previous record's.
+    TimeNotMonotonic { previous_at: u64, at: u64 },
+    /// 🎲 was already used earlier in the chain.
+    DuplicateNonce { nonce: String, first_index: usize },
+    /// 🌐 isn't the scope being verified.
+    WrongScope { found: String },
+    /// Record has no ⬇️🧬, so nothing can link to it.
+    NotFinalized,
+    /// 🪦 doesn't name a later record:ban in the chain aimed at this record.
+    UnbannedTombstone { ban: [u8; 32] },
+    /// The record itself failed validation (hash, signatures, size...).
+    Invalid(RhexError),
+}
+
+#[derive(Debug)]
+pub struct ChainIssue {
+    /// Position in the chain, genesis is 0.
+    pub index: usize,
+    pub current_hash: Option<[u8; 32]>,
+    pub kind: ChainIssueKind,
+}
+
+/// Everything found while walking a scope's chain.
+#[derive(Debug, Default)]
+pub struct ChainReport {
+    pub scope: String,
+    pub records: usize,
+    /// Banned records standing in as tombstones. Their 📊 is gone, but
+    /// their hash and signatures are still checked.
+    pub tombstones: Vec<usize>,
+    /// ⬇️🧬 of the last record walked.
+    pub head: Option<[u8; 32]>,
+    pub issues: Vec<ChainIssue>,
+}
+
+impl ChainReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Index of the first record with a problem, if any.
+    pub fn first_bad_index(&self) -> Option<usize> {
+        self.issues.iter().map(|i| i.index).min()
+    }
+}
+
+/// Walk `records` in order and check them as one scope's chain: genesis
+/// points at the zero hash, every record links to its predecessor, ⏱️ keeps
+/// moving forward, 🎲 never repeats, 🌐 matches, each record validates and
+/// each tombstone was banned by a record:ban later in the chain. Keeps
+/// going after a problem so the report lists all of them.
+pub fn verify_chain<I>(scope: &str, records: I) -> ChainReport
+where
+    I: IntoIterator,
+    I::Item: Borrow<Rhex>,
+{
+    let mut report = ChainReport {
+        scope: scope.to_string(),
+        ..ChainReport::default()
+    };
+    let mut nonces: HashMap<String, usize> = HashMap::new();
+    let mut prev: Option<(Option<[u8; 32]>, u64)> = None;
+    // Signatures are checked in one go once the walk is done.
+    let mut batch = BatchVerifier::new();
+    let mut hashes: Vec<Option<[u8; 32]>> = Vec::new();
+    // ⬇️🧬 of each record:ban → (index, 🎯), and each tombstone's 🪦.
+    let mut bans: HashMap<[u8; 32], (usize, [u8; 32])> = HashMap::new();
+    let mut banned_by: Vec<(usize, [u8; 32])> = Vec::new();
+
+    for (index, item) in records.into_iter().enumerate() {
+        let rhex = item.borrow();
+        let current_hash = rhex.current_hash;
+        let mut issue = |kind| {
+            report.issues.push(ChainIssue {
+                index,
+                current_hash,
+                kind,
+            })
+        };
+
+        if rhex.intent.scope != scope {
+            issue(ChainIssueKind::WrongScope {
+                found: rhex.intent.scope.clone(),
+            });
+        }
+
+        match prev {
+            None => {
+                if rhex.intent.previous_hash != [0u8; 32] {
+                    issue(ChainIssueKind::GenesisNotZero {
+                        previous_hash: rhex.intent.previous_hash,
+                    });
+                }
+            }
+            Some((prev_hash, prev_at)) => {
+                // An unfinalized predecessor was already reported.
+                if let Some(expected) = prev_hash
+                    && rhex.intent.previous_hash != expected
+                {
+                    issue(ChainIssueKind::BrokenLink {
+                        expected,
+                        found: rhex.intent.previous_hash,
+                    });
+                }
+                if rhex.context.at <= prev_at {
+                    issue(ChainIssueKind::TimeNotMonotonic {
+                        previous_at: prev_at,
+                        at: rhex.context.at,
+                    });
+                }
+            }
+        }
+
+        match nonces.get(&rhex.intent.nonce) {
+            Some(&first_index) => issue(ChainIssueKind::DuplicateNonce {
+                nonce: rhex.intent.nonce.clone(),
+                first_index,
+            }),
+            None => {
+                nonces.insert(rhex.intent.nonce.clone(), index);
+            }
+        }
+
+        if current_hash.is_none() {
+            issue(ChainIssueKind::NotFinalized);
+        }
+        let structure = if let Some(ban) = rhex.tombstoned_by() {
+            report.tombstones.push(index);
+            banned_by.push((index, ban));
+            rhex.validate_tombstone()
+        } else {
+            if let (Some(hash), Ok(RecordBody::RecordBan(ban))) =
+                (current_hash, RecordBody::from_rhex(rhex))
+            {
+                bans.insert(hash, (index, ban.target));
+            }
+            rhex.validate_structure()
+        };
+        if let Err(e) = structure.and_then(|_| batch.push(index, rhex)) {
+            issue(ChainIssueKind::Invalid(e));
+        }
+
+        prev = Some((current_hash, rhex.context.at));
+        report.records += 1;
+        report.head = current_hash;
+        hashes.push(current_hash);
+    }
+
+    for (index, ban) in banned_by {
+        let banned = bans
+            .get(&ban)
+            .is_some_and(|&(ban_index, target)| ban_index > index && Some(target) == hashes[index]);
+        if !banned {
+            report.issues.push(ChainIssue {
+                index,
+                current_hash: hashes[index],
+                kind: ChainIssueKind::UnbannedTombstone { ban },
+            });
+        }
+    }
+    for (index, e) in batch.verify() {
+        report.issues.push(ChainIssue {
+            index,
+            current_hash: hashes[index],
+            kind: ChainIssueKind::Invalid(e),
+        });
+    }
+    report.issues.sort_by_key(|i| i.index);
+
+    report
+}
+
+impl fmt::Display for ChainIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainIssueKind::GenesisNotZero { previous_hash } => write!(
+                f,
+                "genesis ⬅️🧬 is {} instead of zero",
+                to_base64(previous_hash)
+            ),
+            ChainIssueKind::BrokenLink { expected, found } => write!(
+                f,
+                "⬅️🧬 is {}, previous ⬇️🧬 is {}",
+                to_base64(found),
+                to_base64(expected)
+            ),
+            ChainIssueKind::TimeNotMonotonic { previous_at, at } => {
+                write!(
+                    f,
+                    "⏱️ {} is not after previous ⏱️ {}",
+                    GtTime::from(*at),
+                    GtTime::from(*previous_at)
+                )
+            }
+            ChainIssueKind::DuplicateNonce { nonce, first_index } => {
+                write!(f, "🎲 {nonce} already used at #{first_index}")
+            }
+            ChainIssueKind::WrongScope { found } => write!(f, "record is for 🌐 {found}"),
+            ChainIssueKind::NotFinalized => write!(f, "record has no ⬇️🧬"),
+            ChainIssueKind::UnbannedTombstone { ban } => write!(
+                f,
+                "🪦 names {}, which isn't a later record:ban of this record",
+                to_base64(ban)
+            ),
+            ChainIssueKind::Invalid(e) => write!(f, "[{}] {e}", e.code()),
+        }
+    }
+}
+
+impl fmt::Display for ChainIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.current_hash {
+            Some(h) => write!(f, "#{} {}: {}", self.index, to_base64(h), self.kind),
+            None => write!(f, "#{}: {}", self.index, self.kind),
+        }
+    }
+}
+
+impl fmt::Display for ChainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "🌐 {}: {} record(s), {} issue(s)",
+            self.scope,
+            self.records,
+            self.issues.len()
+        )?;
+        if !self.tombstones.is_empty() {
+            writeln!(
+                f,
+                "  🪦 {} banned record(s), checked without their 📊",
+                self.tombstones.len()
+            )?;
+        }
+        for issue in &self.issues {
+            writeln!(f, "  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intent, Key, Signature};
+
+    fn record(previous_hash: [u8; 32], nonce: &str, at: u64) -> Rhex {
+        signed(
+            previous_hash,
+            nonce,
+            at,
+            "record:text",
+            serde_json::json!({ "text": nonce }),
+        )
+    }
+
+    fn signed(
+        previous_hash: [u8; 32],
+        nonce: &str,
+        at: u64,
+        record_type: &str,
+        data: serde_json::Value,
+    ) -> Rhex {
+        let author = Key::from_bytes(&[1u8; 32]);
+        let mut rhex = Rhex::draft(Intent::new(
+            &previous_hash,
+            "core",
+            nonce,
+            &author.to_bytes(),
+            &[0u8; 32],
+            record_type,
+            data,
+        ));
+        rhex.context.at = at;
+        let pre = rhex.author_prehash().unwrap();
+        rhex.signatures.push(Signature {
+            sig_type: 0,
+            public_key: author.to_bytes(),
+            sig: author.sign(&pre).unwrap().to_bytes(),
+        });
+        rhex.finalize().unwrap()
+    }
+
+    fn chain() -> Vec<Rhex> {
+        let a = record([0u8; 32], "a", 1);
+        let b = record(a.current_hash.unwrap(), "b", 2);
+        let c = record(b.current_hash.unwrap(), "c", 3);
+        vec![a, b, c]
+    }
+
+    #[test]
+    fn clean_chain() {
+        let records = chain();
+        let report = verify_chain("core", &records);
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.records, 3);
+        assert_eq!(report.head, records[2].current_hash);
+    }
+
+    /// [`chain`] plus a record:ban of `target`.
+    fn banned_chain(target: usize) -> (Vec<Rhex>, [u8; 32]) {
+        let mut records = chain();
+        let ban = signed(
+            records[2].current_hash.unwrap(),
+            "d",
+            4,
+            "record:ban",
+            serde_json::json!({ "🎯": to_base64(&records[target].current_hash.unwrap()) }),
+        );
+        let ban_hash = ban.current_hash.unwrap();
+        records.push(ban);
+        (records, ban_hash)
+    }
+
+    #[test]
+    fn tombstones_keep_the_links() {
+        let (mut records, ban) = banned_chain(1);
+        records[1] = records[1].tombstone(&ban).unwrap();
+        let report = verify_chain("core", &records);
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.tombstones, vec![1]);
+        assert_eq!(records[1].tombstoned_by(), Some(ban));
+    }
+
+    #[test]
+    fn tombstones_need_their_ban() {
+        let unbanned = |records: &[Rhex]| {
+            verify_chain("core", records)
+                .issues
+                .iter()
+                .any(|i| i.index == 1 && matches!(i.kind, ChainIssueKind::UnbannedTombstone { .. }))
+        };
+        // No such ban.
+        let (mut records, _) = banned_chain(1);
+        records[1] = records[1].tombstone(&[9u8; 32]).unwrap();
+        assert!(unbanned(&records));
+        // A ban, but of another record.
+        let (mut records, ban) = banned_chain(2);
+        records[1] = records[1].tombstone(&ban).unwrap();
+        assert!(unbanned(&records));
+        // A record that isn't a ban at all.
+        let (mut records, _) = banned_chain(1);
+        let not_a_ban = records[2].current_hash.unwrap();
+        records[1] = records[1].tombstone(&not_a_ban).unwrap();
+        assert!(unbanned(&records));
+    }
+
+    #[test]
+    fn tampered_tombstones_fail() {
+        let (records, ban) = banned_chain(1);
+        let good = records[1].tombstone(&ban).unwrap();
+        let forged: [fn(&mut Rhex); 4] = [
+            |r| r.context.at = 7,
+            |r| r.intent.author_public_key = [7u8; 32],
+            |r| r.signatures[0].sig[0] ^= 1,
+            |r| {
+                r.intent.data[Rhex::TOMBSTONE_CONTENT_FIELD] = to_base64(&[7u8; 32]).into();
+            },
+        ];
+        for forge in forged {
+            let mut records = records.clone();
+            records[1] = good.clone();
+            forge(&mut records[1]);
+            let report = verify_chain("core", &records);
+            assert!(
+                report
+                    .issues
+                    .iter()
+                    .any(|i| i.index == 1 && matches!(i.kind, ChainIssueKind::Invalid(_))),
+                "{report}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_every_issue_with_position() {
+        let mut records = chain();
+        // Out-of-order time and a reused nonce on the last record.
+        let c = record(records[1].current_hash.unwrap(), "a", 2);
+        records[2] = c;
+        // Tamper with the middle record's data.
+        records[1].intent.data = serde_json::json!({ "text": "evil" });
+
+        let report = verify_chain("core", &records);
+        assert_eq!(report.first_bad_index(), Some(1));
+        let kinds: Vec<(usize, &ChainIssueKind)> =
+            report.issues.iter().map(|i| (i.index, &i.kind)).collect();
+        assert!(matches!(kinds[0], (1, ChainIssueKind::Invalid(_))));
+        assert!(
+            kinds
+                .iter()
+                .any(|k| matches!(k, (2, ChainIssueKind::TimeNotMonotonic { .. })))
+        );
+        assert!(kinds.iter().any(|k| matches!(
+            k,
+            (2, ChainIssueKind::DuplicateNonce { first_index: 0, .. })
+        )));
+    }
+}
@@ -0,0 +1,157 @@
+use super::signature::SigType;
+use crate::crypto::b64::to_base64;
+use std::fmt;
+
+/// Everything that can go wrong while handling a R⬢.
+///
+/// Each variant has a stable [`code`](RhexError::code) so clients and
+/// ushers can react to failures without parsing messages.
+#[derive(Debug)]
+pub enum RhexError {
+    /// Magic does not start with "RHEX".
+    BadMagic,
+    /// Magic carries a version we don't speak.
+    UnsupportedVersion(u8),
+    /// Magic has flag bits set that we don't know about.
+    UnknownFlags(u8),
+    /// Record has no current_hash yet.
+    NotFinalized,
+    /// Stored current_hash doesn't match the recomputed one.
+    HashMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+    /// No signature with type 0.
+    MissingAuthorSignature,
+    /// Signature type outside of author/usher/quorum.
+    InvalidSignatureType(u8),
+    /// Public key bytes are not a valid ed25519 point.
+    InvalidPublicKey { role: SigType, index: usize },
+    /// Signature didn't verify against its prehash.
+    InvalidSignature { role: SigType, index: usize },
+    /// Signature is by a key other than the one 🎯 names.
+    KeyMismatch { role: SigType, index: usize },
+    /// Record or field is larger than allowed.
+    SizeLimitExceeded {
+        what: &'static str,
+        size: usize,
+        limit: usize,
+    },
+    /// Couldn't turn the record into bytes.
+    Encoding(String),
+    /// Couldn't turn bytes into a record.
+    Decoding(String),
+    /// Disk or socket failure.
+    Io(std::io::Error),
+    /// Cache (sqlite) failure.
+    Cache(String),
+}
+
+impl RhexError {
+    /// Stable, machine readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RhexError::BadMagic => "bad_magic",
+            RhexError::UnsupportedVersion(_) => "unsupported_version",
+            RhexError::UnknownFlags(_) => "unknown_flags",
+            RhexError::NotFinalized => "not_finalized",
+            RhexError::HashMismatch { .. } => "hash_mismatch",
+            RhexError::MissingAuthorSignature => "missing_author_signature",
+            RhexError::InvalidSignatureType(_) => "invalid_signature_type",
+            RhexError::InvalidPublicKey { .. } => "invalid_public_key",
+            RhexError::InvalidSignature { .. } => "invalid_signature",
+            RhexError::KeyMismatch { .. } => "key_mismatch",
+            RhexError::SizeLimitExceeded { .. } => "size_limit_exceeded",
+            RhexError::Encoding(_) => "encoding",
+            RhexError::Decoding(_) => "decoding",
+            RhexError::Io(_) => "io",
+            RhexError::Cache(_) => "cache",
+        }
+    }
+
+    pub fn encoding(e: impl fmt::Display) -> Self {
+        RhexError::Encoding(e.to_string())
+    }
+
+    pub fn decoding(e: impl fmt::Display) -> Self {
+        RhexError::Decoding(e.to_string())
+    }
+}
+
+fn role_name(role: &SigType) -> &'static str {
+    match role {
+        SigType::Author => "author",
+        SigType::Usher => "usher",
+        SigType::Quorum => "quorum",
+    }
+}
+
+impl fmt::Display for RhexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RhexError::BadMagic => write!(f, "invalid magic"),
+            RhexError::UnsupportedVersion(v) => write!(f, "unsupported R⬢ version {v}"),
+            RhexError::UnknownFlags(bits) => write!(f, "unknown magic flags {bits:#010b}"),
+            RhexError::NotFinalized => write!(f, "Rhex not finalized: current_hash is None"),
+            RhexError::HashMismatch { expected, actual } => write!(
+                f,
+                "current_hash mismatch: expected {}, got {}",
+                to_base64(expected),
+                to_base64(actual)
+            ),
+            RhexError::MissingAuthorSignature => write!(f, "missing author signature"),
+            RhexError::InvalidSignatureType(t) => write!(f, "invalid signature type: {t}"),
+            RhexError::InvalidPublicKey { role, index } => {
+                write!(f, "invalid {} public key at index {index}", role_name(role))
+            }
+            RhexError::InvalidSignature { role, index } => {
+                write!(f, "invalid {} signature at index {index}", role_name(role))
+            }
+            RhexError::KeyMismatch { role, index } => write!(
+                f,
+                "{} signature at index {index} isn't by the key 🎯 names",
+                role_name(role)
+            ),
+            RhexError::SizeLimitExceeded { what, size, limit } => {
+                write!(f, "{what} is {size} bytes, limit is {limit}")
+            }
+            RhexError::Encoding(e) => write!(f, "encoding failed: {e}"),
+            RhexError::Decoding(e) => write!(f, "decoding failed: {e}"),
+            RhexError::Io(e) => write!(f, "io error: {e}"),
+            RhexError::Cache(e) => write!(f, "cache error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RhexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RhexError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RhexError {
+    fn from(e: std::io::Error) -> Self {
+        RhexError::Io(e)
+    }
+}
+
+impl From<serde_cbor::Error> for RhexError {
+    fn from(e: serde_cbor::Error) -> Self {
+        RhexError::Decoding(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for RhexError {
+    fn from(e: serde_json::Error) -> Self {
+        RhexError::Decoding(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for RhexError {
+    fn from(e: rusqlite::Error) -> Self {
+        RhexError::Cache(e.to_string())
+    }
+}
@@ -0,0 +1,128 @@
+use super::{
+    error::RhexError,
+    magic::{VERSION_0, VERSION_1},
+    rhex::Rhex,
+};
+use crate::policy::policy::Policy;
+
+/// Largest thing we'll ever put on the wire, whatever a version says.
+pub const HARD_MAX_RECORD_BYTES: usize = 4096;
+
+/// Size caps for a R⬢. Sizes are measured on the canonical CBOR encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordLimits {
+    /// Whole packed record.
+    pub max_record_bytes: usize,
+    /// 📊 on its own.
+    pub max_data_bytes: usize,
+}
+
+impl RecordLimits {
+    /// The canon: records ≤1.5 KB, 📊 ≤1024 bytes.
+    pub const V0: RecordLimits = RecordLimits {
+        max_record_bytes: 1536,
+        max_data_bytes: 1024,
+    };
+    pub const V1: RecordLimits = RecordLimits {
+        max_record_bytes: 1536,
+        max_data_bytes: 1024,
+    };
+
+    pub fn for_version(version: u8) -> Result<Self, RhexError> {
+        match version {
+            VERSION_0 => Ok(Self::V0),
+            VERSION_1 => Ok(Self::V1),
+            v => Err(RhexError::UnsupportedVersion(v)),
+        }
+    }
+
+    pub fn for_rhex(rhex: &Rhex) -> Result<Self, RhexError> {
+        Self::for_version(rhex.magic()?.version())
+    }
+
+    /// Apply a scope policy's overrides. Policies can only tighten the
+    /// limits of the format version, never loosen them.
+    pub fn with_policy(self, policy: &Policy) -> Self {
+        Self {
+            max_record_bytes: policy
+                .max_record_bytes
+                .map_or(self.max_record_bytes, |p| p.min(self.max_record_bytes)),
+            max_data_bytes: policy
+                .max_data_bytes
+                .map_or(self.max_data_bytes, |p| p.min(self.max_data_bytes)),
+        }
+    }
+
+    pub fn check_data_len(&self, size: usize) -> Result<(), RhexError> {
+        if size > self.max_data_bytes {
+            return Err(RhexError::SizeLimitExceeded {
+                what: "📊 data",
+                size,
+                limit: self.max_data_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn check_record_len(&self, size: usize) -> Result<(), RhexError> {
+        if size > self.max_record_bytes {
+            return Err(RhexError::SizeLimitExceeded {
+                what: "R⬢ record",
+                size,
+                limit: self.max_record_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Measure and check both 📊 and the whole record.
+    pub fn check(&self, rhex: &Rhex) -> Result<(), RhexError> {
+        let data_len = Rhex::to_stable_cbor(&rhex.intent.data)
+            .map_err(RhexError::encoding)?
+            .len();
+        self.check_data_len(data_len)?;
+        self.check_record_len(rhex.pack()?.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Intent;
+
+    fn draft_with_text(len: usize) -> Rhex {
+        Rhex::draft(Intent::new(
+            &[0u8; 32],
+            "core",
+            "AAAAAAAAAAAAAAAA",
+            &[0u8; 32],
+            &[0u8; 32],
+            "record:text",
+            serde_json::json!({ "text": "x".repeat(len) }),
+        ))
+    }
+
+    #[test]
+    fn data_cap_reports_sizes() {
+        let rhex = draft_with_text(1100);
+        match RecordLimits::V1.check(&rhex) {
+            Err(RhexError::SizeLimitExceeded { size, limit, .. }) => {
+                assert!(size > 1100);
+                assert_eq!(limit, 1024);
+            }
+            other => panic!("expected size error, got {other:?}"),
+        }
+        RecordLimits::V1.check(&draft_with_text(100)).unwrap();
+    }
+
+    #[test]
+    fn policy_only_tightens() {
+        let mut policy = Policy::new("core", Vec::new());
+        policy.max_data_bytes = Some(64);
+        policy.max_record_bytes = Some(100_000);
+        let limits = RecordLimits::V1.with_policy(&policy);
+        assert_eq!(limits.max_data_bytes, 64);
+        assert_eq!(limits.max_record_bytes, RecordLimits::V1.max_record_bytes);
+        assert!(limits.check(&draft_with_text(100)).is_err());
+    }
+}
@@ -1,11 +1,184 @@
-pub fn explode_magic(magic: &[u8; 32]) -> Result<([u8; 4], u8, u8), anyhow::Error> {
-    let name: [u8; 4] = magic[0..4].try_into().unwrap();
-    let flags = magic[4];
-    let version = magic[5];
-    Ok((name, flags, version))
-}
+use super::error::RhexError;
+
+/// 🪄 layout: `"RHEX"` + 1 byte of flags + 1 byte of version.
+pub const MAGIC_PREFIX: &[u8; 4] = b"RHEX";
 
 pub const MAGIC_MORE: u8 = 1 << 0;
 pub const MAGIC_RESUME: u8 = 1 << 1;
 pub const MAGIC_METADATA: u8 = 1 << 2;
 pub const MAGIC_ENCRYPTION: u8 = 1 << 3;
+
+/// Every flag bit we know how to handle. Anything else is rejected.
+pub const MAGIC_KNOWN_FLAGS: u8 = MAGIC_MORE | MAGIC_RESUME | MAGIC_METADATA | MAGIC_ENCRYPTION;
+
+/// Original format. The magic bytes are not covered by any hash.
+pub const VERSION_0: u8 = 0;
+/// Magic bytes are bound into the author prehash (and so into current_hash).
+pub const VERSION_1: u8 = 1;
+/// Version new records are written with.
+pub const CURRENT_VERSION: u8 = VERSION_1;
+/// Versions this reader understands.
+pub const SUPPORTED_VERSIONS: &[u8] = &[VERSION_0, VERSION_1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Magic {
+    flags: u8,
+    version: u8,
+}
+
+impl Default for Magic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Magic {
+    /// Current version, no flags set.
+    pub fn new() -> Self {
+        Self {
+            flags: 0,
+            version: CURRENT_VERSION,
+        }
+    }
+
+    pub fn with_version(version: u8) -> Result<Self, RhexError> {
+        if !SUPPORTED_VERSIONS.contains(&version) {
+            return Err(RhexError::UnsupportedVersion(version));
+        }
+        Ok(Self { flags: 0, version })
+    }
+
+    /// Parse and validate raw magic bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RhexError> {
+        if bytes.len() != 6 || &bytes[0..4] != MAGIC_PREFIX {
+            return Err(RhexError::BadMagic);
+        }
+        let mut magic = Self::with_version(bytes[5])?;
+        magic.set_flags(bytes[4])?;
+        Ok(magic)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 6] {
+        let mut out = [0u8; 6];
+        out[0..4].copy_from_slice(MAGIC_PREFIX);
+        out[4] = self.flags;
+        out[5] = self.version;
+        out
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Replace all flags at once.
+    pub fn set_flags(&mut self, flags: u8) -> Result<(), RhexError> {
+        if flags & !MAGIC_KNOWN_FLAGS != 0 {
+            return Err(RhexError::UnknownFlags(flags & !MAGIC_KNOWN_FLAGS));
+        }
+        self.flags = flags;
+        Ok(())
+    }
+
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags & flag == flag
+    }
+
+    pub fn set_flag(&mut self, flag: u8, on: bool) -> Result<(), RhexError> {
+        let flags = if on {
+            self.flags | flag
+        } else {
+            self.flags & !flag
+        };
+        self.set_flags(flags)
+    }
+
+    /// More records follow this one as part of the same message.
+    pub fn is_more(&self) -> bool {
+        self.has_flag(MAGIC_MORE)
+    }
+
+    /// This record resumes an earlier interrupted message.
+    pub fn is_resume(&self) -> bool {
+        self.has_flag(MAGIC_RESUME)
+    }
+
+    /// 📊 carries metadata rather than record content.
+    pub fn is_metadata(&self) -> bool {
+        self.has_flag(MAGIC_METADATA)
+    }
+
+    /// 📊 is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.has_flag(MAGIC_ENCRYPTION)
+    }
+
+    /// Whether the magic bytes are part of the author prehash.
+    pub fn is_hash_bound(&self) -> bool {
+        self.version >= VERSION_1
+    }
+}
+
+pub fn explode_magic(magic: &[u8; 6]) -> Result<([u8; 4], u8, u8), RhexError> {
+    let parsed = Magic::from_bytes(magic)?;
+    Ok((*MAGIC_PREFIX, parsed.flags(), parsed.version()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intent, Key, Rhex, Signature};
+
+    #[test]
+    fn parse_and_reject() {
+        let m = Magic::from_bytes(b"RHEX\x08\x01").unwrap();
+        assert!(m.is_encrypted());
+        assert_eq!(m.version(), VERSION_1);
+        assert_eq!(&m.to_bytes(), b"RHEX\x08\x01");
+
+        assert!(matches!(
+            Magic::from_bytes(b"RHEY\x00\x00"),
+            Err(RhexError::BadMagic)
+        ));
+        assert!(matches!(
+            Magic::from_bytes(b"RHEX\x00\x09"),
+            Err(RhexError::UnsupportedVersion(9))
+        ));
+        assert!(matches!(
+            Magic::from_bytes(b"RHEX\x80\x01"),
+            Err(RhexError::UnknownFlags(0x80))
+        ));
+    }
+
+    #[test]
+    fn v1_binds_flags_into_hash() {
+        let author = Key::from_bytes(&[1u8; 32]);
+        let intent = Intent::new(
+            &[0u8; 32],
+            "core",
+            "AAAAAAAAAAAAAAAA",
+            &author.to_bytes(),
+            &[0u8; 32],
+            "record:text",
+            serde_json::json!({ "text": "hello" }),
+        );
+        let mut rhex = Rhex::draft(intent);
+        let pre = rhex.author_prehash().unwrap();
+        rhex.signatures.push(Signature {
+            sig_type: 0,
+            public_key: author.to_bytes(),
+            sig: author.sign(&pre).unwrap().to_bytes(),
+        });
+        let rhex = rhex.finalize().unwrap();
+        rhex.validate().unwrap();
+
+        let mut flipped = rhex.clone();
+        let mut magic = flipped.magic().unwrap();
+        magic.set_flag(MAGIC_ENCRYPTION, true).unwrap();
+        flipped.set_magic(magic);
+        assert!(flipped.validate().is_err());
+    }
+}
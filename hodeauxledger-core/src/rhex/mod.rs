@@ -1,5 +1,10 @@
+pub mod batch;
+pub mod canonical;
+pub mod chain;
 pub mod context;
+pub mod error;
 pub mod intent;
+pub mod limits;
 pub mod magic;
 pub mod rhex;
 pub mod signature;
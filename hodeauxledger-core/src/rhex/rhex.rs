@@ -1,17 +1,24 @@
-use super::{context::Context, intent::Intent, signature::Signature};
+use super::{
+    batch::SigCheck,
+    canonical,
+    context::Context,
+    error::RhexError,
+    intent::Intent,
+    limits::RecordLimits,
+    magic::{MAGIC_ENCRYPTION, Magic},
+    signature::{SigType, Signature},
+};
+use crate::crypto::b64::{from_base64_to_32, to_base64};
+use crate::crypto::seal::{self, SealedData};
 use crate::key::key::Key;
 
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Result, bail};
 use blake3::Hasher;
-use ed25519_dalek::{Signature as DalekSig, VerifyingKey};
 use rand::{Rng, distr::Alphanumeric};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::cmp::Ordering;
 
-const MAGIC_PREFIX: &[u8; 4] = b"RHEX";
-const MAGIC_V1: [u8; 6] = *b"RHEX\x00\x00";
-
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Rhex {
@@ -36,7 +43,7 @@ pub struct Rhex {
 impl Rhex {
     pub fn new() -> Self {
         Self {
-            magic: MAGIC_V1,
+            magic: Magic::new().to_bytes(),
             intent: Intent::new(&[0u8; 32], "", "", &[0u8; 32], &[0u8; 32], "", "{}".into()),
             context: Context::new(),
             signatures: Vec::new(),
@@ -46,7 +53,7 @@ impl Rhex {
 
     pub fn draft(intent: Intent) -> Self {
         Self {
-            magic: MAGIC_V1,
+            magic: Magic::new().to_bytes(),
             intent,
             context: Context { at: 0 },
             signatures: Vec::new(),
@@ -63,10 +70,15 @@ impl Rhex {
     pub const DOMAIN_USHER: &'static [u8] = b"RSIG/U/1";
     pub const DOMAIN_QUORUM: &'static [u8] = b"RSIG/Q/1";
 
-    /// Author prehash: H("RHEXv1|CONTENT" || c14n(intent))
+    /// Author prehash: H("RHEXv1|CONTENT" || c14n(intent)) for version 0,
+    /// H("RHEXv1|CONTENT" || magic || c14n(intent)) from version 1 on.
     pub fn author_prehash(&self) -> Result<[u8; 32]> {
+        let magic = self.magic()?;
         let mut h = Hasher::new();
         h.update(Self::DOMAIN_CONTENT);
+        if magic.is_hash_bound() {
+            h.update(&magic.to_bytes());
+        }
         h.update(&Self::to_stable_cbor(&self.intent)?);
         Ok(h.finalize().into())
     }
@@ -113,12 +125,15 @@ impl Rhex {
 
     /// current_hash = H("RHEXv1|RECORD" || content_prehash || context.at_be || canonical(sigs))
     pub fn compute_current_hash(&self) -> Result<[u8; 32]> {
-        let content = self.author_prehash()?;
+        self.record_hash(&self.author_prehash()?)
+    }
+
+    fn record_hash(&self, content: &[u8; 32]) -> Result<[u8; 32]> {
         let sigs = self.canonical_sigs_bytes()?;
 
         let mut h = Hasher::new();
         h.update(Self::DOMAIN_RECORD);
-        h.update(&content);
+        h.update(content);
         h.update(&self.context.at.to_be_bytes());
         h.update(&sigs);
         Ok(h.finalize().into())
@@ -129,64 +144,100 @@ impl Rhex {
         Ok(self)
     }
 
-    pub fn current_hash(&self) -> Result<[u8; 32]> {
-        self.current_hash
-            .ok_or_else(|| anyhow!("Rhex not finalized: current_hash is None"))
+    pub fn current_hash(&self) -> Result<[u8; 32], RhexError> {
+        self.current_hash.ok_or(RhexError::NotFinalized)
     }
 }
 
 /* ───────────────────────────────  Validation & Sig  ─────────────────────────── */
 
 impl Rhex {
-    pub fn validate(&self) -> Result<()> {
-        if self.magic.len() != 6 || &self.magic[0..4] != MAGIC_PREFIX {
-            bail!("invalid magic");
+    pub fn validate(&self) -> Result<(), RhexError> {
+        self.validate_structure()?;
+        for check in self.signature_checks()? {
+            check.verify()?;
+        }
+        Ok(())
+    }
+
+    /// Everything [`Rhex::validate`] checks except the ed25519 math itself:
+    /// 🪄, size limits, sealed 📊 and ⬇️🧬.
+    pub fn validate_structure(&self) -> Result<(), RhexError> {
+        let magic = self.magic()?;
+        if magic.is_encrypted() {
+            SealedData::from_value(&self.intent.data).map_err(RhexError::decoding)?;
         }
+        RecordLimits::for_version(magic.version())?.check(self)?;
 
         if let Some(ch) = self.current_hash {
-            let recomputed = self.compute_current_hash()?;
+            let recomputed = self.compute_current_hash().map_err(RhexError::encoding)?;
             if recomputed != ch {
-                bail!("current_hash mismatch");
+                return Err(RhexError::HashMismatch {
+                    expected: ch,
+                    actual: recomputed,
+                });
             }
         }
+        Ok(())
+    }
+
+    /// The (prehash, signature, key) triples this record's signatures have
+    /// to satisfy, so they can be verified one by one or batched.
+    pub fn signature_checks(&self) -> Result<Vec<SigCheck>, RhexError> {
+        let author_pre = self.author_prehash().map_err(RhexError::encoding)?;
+        self.signature_checks_over(author_pre)
+    }
+
+    fn signature_checks_over(&self, author_pre: [u8; 32]) -> Result<Vec<SigCheck>, RhexError> {
+        if let Some(bad) = self.signatures.iter().find(|s| s.sig_type > 2) {
+            return Err(RhexError::InvalidSignatureType(bad.sig_type));
+        }
+        let mut checks = Vec::with_capacity(self.signatures.len());
 
         // Author (required) over author_prehash
-        let author = self
+        let (author_idx, author) = self
             .signatures
             .iter()
-            .find(|s| s.sig_type == 0)
-            .ok_or_else(|| anyhow!("missing author signature"))?;
-        let author_pre = self.author_prehash()?;
-        Self::verify_one(author, &author_pre, "author")?;
+            .enumerate()
+            .find(|(_, s)| s.sig_type == 0)
+            .ok_or(RhexError::MissingAuthorSignature)?;
+        checks.push(SigCheck::new(
+            author,
+            author_pre,
+            SigType::Author,
+            author_idx,
+        )?);
 
         // Usher (optional) over usher_prehash(author.sig)
-        let usher_opt = self.signatures.iter().find(|s| s.sig_type == 1);
-        if let Some(usher) = usher_opt {
-            let pre = self.usher_prehash(&author.sig)?;
-            Self::verify_one(usher, &pre, "usher")?;
+        let usher_opt = self
+            .signatures
+            .iter()
+            .enumerate()
+            .find(|(_, s)| s.sig_type == 1);
+        if let Some((i, usher)) = usher_opt {
+            let pre = self
+                .usher_prehash(&author.sig)
+                .map_err(RhexError::encoding)?;
+            checks.push(SigCheck::new(usher, pre, SigType::Usher, i)?);
         }
 
         // Quorum (optional) over quorum_prehash(author.sig, usher.sig)
-        let quorum: Vec<&Signature> = self.signatures.iter().filter(|s| s.sig_type == 2).collect();
+        let quorum: Vec<(usize, &Signature)> = self
+            .signatures
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.sig_type == 2)
+            .collect();
         if !quorum.is_empty() {
-            let pre = self.quorum_prehash(&author.sig, usher_opt.map(|u| &u.sig))?;
-            for (i, q) in quorum.into_iter().enumerate() {
-                Self::verify_one(q, &pre, &format!("quorum[{i}]"))?;
+            let pre = self
+                .quorum_prehash(&author.sig, usher_opt.map(|(_, u)| &u.sig))
+                .map_err(RhexError::encoding)?;
+            for (i, q) in quorum {
+                checks.push(SigCheck::new(q, pre, SigType::Quorum, i)?);
             }
         }
 
-        Ok(())
-    }
-
-    fn verify_one(sig: &Signature, msg_hash32: &[u8; 32], label: &str) -> Result<()> {
-        let pk = VerifyingKey::from_bytes(&sig.public_key)?;
-        let mut k = Key::new();
-        k.set_pub_key(pk);
-        let dalek = DalekSig::from_bytes(&sig.sig);
-        if !k.verify(msg_hash32, &dalek) {
-            bail!("invalid {label} signature");
-        }
-        Ok(())
+        Ok(checks)
     }
 }
 
@@ -211,23 +262,155 @@ impl Rhex {
     }
 }
 
+/* ──────────────────────────────────  Tombstones  ────────────────────────────── */
+
+impl Rhex {
+    /// 📊 key of a tombstone holding the ⬇️🧬 of the record:ban.
+    pub const TOMBSTONE_FIELD: &'static str = "🪦";
+    /// 📊 key of a tombstone holding the banned record's author prehash.
+    pub const TOMBSTONE_CONTENT_FIELD: &'static str = "🧬";
+
+    /// Stand-in for a banned record. 📊 gives way to the ban's ⬇️🧬 and the
+    /// record's author prehash; everything else is kept, so the record
+    /// still links into the chain and [`Rhex::validate_tombstone`] can
+    /// still check its ⬇️🧬 and signatures.
+    pub fn tombstone(&self, ban_hash: &[u8; 32]) -> Result<Rhex> {
+        let content = self.author_prehash()?;
+        let mut out = self.clone();
+        out.intent.data = serde_json::json!({
+            Self::TOMBSTONE_FIELD: to_base64(ban_hash),
+            Self::TOMBSTONE_CONTENT_FIELD: to_base64(&content),
+        });
+        Ok(out)
+    }
+
+    pub fn is_tombstone(&self) -> bool {
+        self.tombstone_fields().is_some()
+    }
+
+    /// ⬇️🧬 of the record:ban this tombstone stands in for.
+    pub fn tombstoned_by(&self) -> Option<[u8; 32]> {
+        self.tombstone_fields().map(|(ban, _)| ban)
+    }
+
+    fn tombstone_fields(&self) -> Option<([u8; 32], [u8; 32])> {
+        let data = self.intent.data.as_object()?;
+        if data.len() != 2 {
+            return None;
+        }
+        let field = |name| from_base64_to_32(data.get(name)?.as_str()?).ok();
+        Some((
+            field(Self::TOMBSTONE_FIELD)?,
+            field(Self::TOMBSTONE_CONTENT_FIELD)?,
+        ))
+    }
+
+    /// What [`Rhex::validate_structure`] checks, for a tombstone: ⬇️🧬 is
+    /// recomputed from the kept author prehash, and the author and usher
+    /// signatures have to be by the keys 🎯 names. 🎯 itself is only as
+    /// good as the record:ban that made the tombstone.
+    pub fn validate_tombstone(&self) -> Result<(), RhexError> {
+        let (_, content) = self
+            .tombstone_fields()
+            .ok_or_else(|| RhexError::decoding("not a 🪦"))?;
+        self.magic()?;
+        let ch = self.current_hash()?;
+        let recomputed = self.record_hash(&content).map_err(RhexError::encoding)?;
+        if recomputed != ch {
+            return Err(RhexError::HashMismatch {
+                expected: ch,
+                actual: recomputed,
+            });
+        }
+        for (index, s) in self.signatures.iter().enumerate() {
+            let named = match s.sig_type {
+                0 => Some((SigType::Author, &self.intent.author_public_key)),
+                1 => Some((SigType::Usher, &self.intent.usher_public_key)),
+                _ => None,
+            };
+            if let Some((role, key)) = named
+                && &s.public_key != key
+            {
+                return Err(RhexError::KeyMismatch { role, index });
+            }
+        }
+        Ok(())
+    }
+
+    /// [`Rhex::signature_checks`] for a tombstone, over the author prehash
+    /// it kept.
+    pub fn tombstone_signature_checks(&self) -> Result<Vec<SigCheck>, RhexError> {
+        let (_, content) = self
+            .tombstone_fields()
+            .ok_or_else(|| RhexError::decoding("not a 🪦"))?;
+        self.signature_checks_over(content)
+    }
+}
+
+/* ──────────────────────────────  Confidential data  ─────────────────────────── */
+
+impl Rhex {
+    pub const DOMAIN_SEAL: &'static [u8] = b"RHEX/SEAL/1";
+
+    /// Seal 📊 to `recipients` (ed25519 public keys) and set the encryption
+    /// flag. Has to happen before the author signs.
+    pub fn seal_data(&mut self, recipients: &[[u8; 32]]) -> Result<()> {
+        if !self.signatures.is_empty() {
+            bail!("cannot seal data on a signed R⬢");
+        }
+        let mut magic = self.magic()?;
+        if magic.is_encrypted() {
+            bail!("📊 is already sealed");
+        }
+        if !magic.is_hash_bound() {
+            bail!("sealed records need magic version 1 or later");
+        }
+        let sealed = seal::seal(&self.intent.data, recipients, &self.seal_aad())?;
+        self.intent.data = sealed.to_value()?;
+        magic.set_flag(MAGIC_ENCRYPTION, true)?;
+        self.set_magic(magic);
+        Ok(())
+    }
+
+    /// Decrypt 📊 with a recipient key. Plain records just hand back their data.
+    pub fn open_data(&self, key: &Key) -> Result<serde_json::Value> {
+        if !self.magic()?.is_encrypted() {
+            return Ok(self.intent.data.clone());
+        }
+        let sealed = SealedData::from_value(&self.intent.data)?;
+        seal::open(&sealed, key, &self.seal_aad())
+    }
+
+    /// Ties the ciphertext to the scope and record type it was sealed for.
+    fn seal_aad(&self) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(
+            Self::DOMAIN_SEAL.len() + self.intent.scope.len() + self.intent.record_type.len() + 1,
+        );
+        aad.extend_from_slice(Self::DOMAIN_SEAL);
+        aad.extend_from_slice(self.intent.scope.as_bytes());
+        aad.push(b'|');
+        aad.extend_from_slice(self.intent.record_type.as_bytes());
+        aad
+    }
+}
+
 /* ───────────────────────────────  (De)serialization  ────────────────────────── */
 
 impl Rhex {
-    pub fn pack(&self) -> Result<Vec<u8>> {
-        Self::to_stable_cbor(self)
+    pub fn pack(&self) -> Result<Vec<u8>, RhexError> {
+        Self::to_stable_cbor(self).map_err(RhexError::encoding)
     }
 
-    pub fn unpack(bytes: &[u8]) -> Result<Self> {
+    pub fn unpack(bytes: &[u8]) -> Result<Self, RhexError> {
         Self::from_cbor(bytes)
     }
 
+    /// Deterministic CBOR (RFC 8949 core deterministic encoding).
     pub fn to_stable_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>> {
-        let v = serde_cbor::value::to_value(value)?;
-        Ok(serde_cbor::to_vec(&v)?)
+        canonical::to_canonical_cbor(value)
     }
 
-    pub fn from_cbor<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
+    pub fn from_cbor<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T, RhexError> {
         Ok(serde_cbor::from_slice(bytes)?)
     }
 }
@@ -235,17 +418,17 @@ impl Rhex {
 /* ───────────────────────────────────  Utils  ────────────────────────────────── */
 
 impl Rhex {
-    pub fn get_version(magic: &[u8]) -> Result<u16> {
-        if magic.len() != 6 {
-            bail!("magic must be 6 bytes, got {}", magic.len());
-        }
-        if &magic[0..4] != MAGIC_PREFIX {
-            bail!("bad magic prefix");
-        }
-        let version_bytes: [u8; 2] = magic[4..6]
-            .try_into()
-            .map_err(|_| anyhow!("bad magic version slice"))?;
-        Ok(u16::from_be_bytes(version_bytes))
+    /// Parsed and validated 🪄.
+    pub fn magic(&self) -> Result<Magic, RhexError> {
+        Magic::from_bytes(&self.magic)
+    }
+
+    pub fn set_magic(&mut self, magic: Magic) {
+        self.magic = magic.to_bytes();
+    }
+
+    pub fn get_version(magic: &[u8]) -> Result<u8, RhexError> {
+        Ok(Magic::from_bytes(magic)?.version())
     }
 
     pub fn gen_nonce() -> String {
@@ -1,5 +1,11 @@
-use crate::to_base64;
+use anyhow::{Result, bail};
 
+use crate::{to_base64, url::url::RhexUrl};
+
+/// Longest alias name we accept.
+pub const MAX_ALIAS_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Alias {
     pub name: String,
     pub scope: String,
@@ -20,4 +26,41 @@ impl Alias {
     pub fn to_resolved(&self) -> String {
         format!("rhex://{}/{}", self.scope, to_base64(&self.hash))
     }
+
+    /// Alias names sit where a ⬇️🧬 goes in a rhex:// URL, so they are
+    /// limited to letters, digits, `-`, `_` and `.`, and must not read as a
+    /// hash themselves.
+    pub fn check_name(name: &str) -> Result<()> {
+        if name.is_empty() {
+            bail!("alias name is empty");
+        }
+        if name.len() > MAX_ALIAS_LEN {
+            bail!("alias name is over {MAX_ALIAS_LEN} characters");
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        {
+            bail!("alias name {name:?} has {c:?}");
+        }
+        if RhexUrl::new("rhex", "", name, None, None).hash().is_some() {
+            bail!("alias name {name:?} reads as a ⬇️🧬");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_cant_pass_for_hashes() {
+        Alias::check_name("policy.current").unwrap();
+        Alias::check_name("schema-key_grant").unwrap();
+        assert!(Alias::check_name("").is_err());
+        assert!(Alias::check_name("a/b").is_err());
+        assert!(Alias::check_name("v@1").is_err());
+        assert!(Alias::check_name(&to_base64(&[7u8; 32])).is_err());
+    }
 }
@@ -1,4 +1,5 @@
 pub mod alias;
 pub mod authority;
+pub mod name;
 pub mod scope;
 pub mod table;
@@ -0,0 +1,211 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// Longest a single segment may be.
+pub const MAX_SEGMENT_LEN: usize = 32;
+/// Most segments a name may have. The root has none.
+pub const MAX_DEPTH: usize = 16;
+
+/// A checked 🌐 name: dot-separated segments of `a-z`, `0-9`, `-` and `_`,
+/// each starting with a letter or digit. The root is the empty name.
+///
+/// Names end up in file paths and are compared byte for byte, so
+/// [`ScopeName::parse`] only takes names already in canonical form.
+/// [`ScopeName::normalize`] gets typed input there first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ScopeName(String);
+
+impl ScopeName {
+    pub fn root() -> Self {
+        Self(String::new())
+    }
+
+    /// Check `name` as it is.
+    pub fn parse(name: &str) -> Result<Self> {
+        if name.is_empty() {
+            return Ok(Self::root());
+        }
+        let normal = normal_form(name);
+        if normal != name {
+            bail!("🌐 name {name:?} isn't in canonical form, write it as {normal:?}");
+        }
+        let depth = name.split('.').count();
+        if depth > MAX_DEPTH {
+            bail!("🌐 name {name:?} is {depth} deep, at most {MAX_DEPTH} allowed");
+        }
+        for segment in name.split('.') {
+            check_segment(name, segment)?;
+        }
+        Ok(Self(name.to_string()))
+    }
+
+    /// NFKC-normalize, lowercase and trim `input`, then check it. For
+    /// names people type; records carry canonical names already.
+    pub fn normalize(input: &str) -> Result<Self> {
+        Self::parse(&normal_form(input.trim()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of segments; 0 for the root.
+    pub fn depth(&self) -> usize {
+        self.segments().count()
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split('.').filter(|s| !s.is_empty())
+    }
+
+    /// The scope directly above, `None` for the root.
+    pub fn parent(&self) -> Option<ScopeName> {
+        if self.is_root() {
+            return None;
+        }
+        Some(match self.0.rsplit_once('.') {
+            Some((parent, _)) => Self(parent.to_string()),
+            None => Self::root(),
+        })
+    }
+
+    pub fn child(&self, segment: &str) -> Result<ScopeName> {
+        if self.is_root() {
+            Self::parse(segment)
+        } else {
+            Self::parse(&format!("{}.{segment}", self.0))
+        }
+    }
+
+    /// Every scope above this one, root first.
+    pub fn ancestors(&self) -> Vec<ScopeName> {
+        let mut out = Vec::new();
+        let mut next = self.parent();
+        while let Some(name) = next {
+            next = name.parent();
+            out.push(name);
+        }
+        out.reverse();
+        out
+    }
+
+    /// Is `other` somewhere below this scope?
+    pub fn is_ancestor_of(&self, other: &ScopeName) -> bool {
+        if self.is_root() {
+            return !other.is_root();
+        }
+        other
+            .0
+            .strip_prefix(&self.0)
+            .is_some_and(|rest| rest.starts_with('.'))
+    }
+}
+
+fn normal_form(s: &str) -> String {
+    s.nfkc().collect::<String>().to_lowercase()
+}
+
+fn check_segment(name: &str, segment: &str) -> Result<()> {
+    if segment.is_empty() {
+        bail!("🌐 name {name:?} has an empty segment");
+    }
+    if segment.len() > MAX_SEGMENT_LEN {
+        bail!("🌐 segment {segment:?} is over {MAX_SEGMENT_LEN} characters");
+    }
+    if let Some(c) = segment
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_')))
+    {
+        bail!("🌐 segment {segment:?} has {c:?}");
+    }
+    if !segment.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        bail!("🌐 segment {segment:?} must start with a letter or digit");
+    }
+    Ok(())
+}
+
+impl fmt::Display for ScopeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ScopeName {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for ScopeName {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl From<ScopeName> for String {
+    fn from(name: ScopeName) -> Self {
+        name.0
+    }
+}
+
+impl AsRef<str> for ScopeName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_what_shouldnt_be_a_path() {
+        for bad in [
+            "../x",
+            "a..b",
+            ".a",
+            "a.",
+            "a/b",
+            "a b",
+            " a",
+            "Core",
+            "core.Trust",
+            "-a",
+            "ｃｏｒｅ",
+        ] {
+            assert!(ScopeName::parse(bad).is_err(), "{bad:?} parsed");
+        }
+        assert!(ScopeName::parse(&"a".repeat(MAX_SEGMENT_LEN + 1)).is_err());
+        assert!(ScopeName::parse(&vec!["a"; MAX_DEPTH + 1].join(".")).is_err());
+        assert_eq!(
+            ScopeName::normalize(" Ｃore.Trust ").unwrap().as_str(),
+            "core.trust"
+        );
+    }
+
+    #[test]
+    fn walks_up_the_tree() {
+        let name = ScopeName::parse("a.b-1.c_2").unwrap();
+        assert_eq!(name.depth(), 3);
+        assert_eq!(name.parent().unwrap().as_str(), "a.b-1");
+        let ancestors: Vec<String> = name.ancestors().into_iter().map(String::from).collect();
+        assert_eq!(ancestors, ["", "a", "a.b-1"]);
+        assert!(ScopeName::root().is_ancestor_of(&name));
+        assert!(ScopeName::parse("a").unwrap().is_ancestor_of(&name));
+        assert!(!ScopeName::parse("a.b").unwrap().is_ancestor_of(&name));
+        assert_eq!(ScopeName::root().parent(), None);
+        assert_eq!(ScopeName::root().child("x").unwrap().as_str(), "x");
+    }
+}
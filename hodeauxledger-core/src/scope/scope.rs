@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    crypto::b64::b64_32_opt,
     policy::policy::Policy,
+    record::record_type::RecordType,
     scope::authority::{self, Authority},
 };
 
@@ -16,6 +18,9 @@ pub struct Scope {
     pub authorities: Vec<Authority>,
     #[serde(default)]
     pub head: [u8; 32],
+    /// ⬇️🧬 of the scope:seal that closed this scope, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "b64_32_opt")]
+    pub sealed: Option<[u8; 32]>,
 }
 
 impl Scope {
@@ -27,22 +32,30 @@ impl Scope {
             policy: Policy::new("", Vec::new()),
             authorities: Vec::new(),
             head: [0u8; 32],
+            sealed: None,
         }
     }
 
+    /// Only sees this scope's own seal, see [`Scope::writable`].
     pub fn can_append_rt(&self, record_type: &str) -> bool {
         if !self.writable() {
             return false;
         }
+        let Ok(record_type) = RecordType::parse(record_type) else {
+            return false;
+        };
         self.policy
             .rules
             .iter()
-            .any(|rule| rule.record_type == record_type)
+            .any(|rule| rule.matches(&record_type))
     }
 
-    /// Is the scope writable or are we just a mirror?
+    /// Is the scope writable, or are we just a mirror, or is it sealed
+    /// itself? A sealed parent also closes this scope, but that isn't seen
+    /// here; appends are held to seals up the lineage by the cached seal
+    /// check.
     pub fn writable(&self) -> bool {
-        matches!(self.role.as_str(), "authority")
+        matches!(self.role.as_str(), "authority") && self.sealed.is_none()
     }
 
     pub fn remove_authority_by_key(&mut self, authority_pk: [u8; 32]) {
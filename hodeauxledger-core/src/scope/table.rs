@@ -1,16 +1,57 @@
-use crate::scope::scope::Scope;
+use std::collections::HashMap;
+
+use crate::scope::{name::ScopeName, scope::Scope};
 use anyhow::Result;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// The scopes we carry, indexed by name. Every name is a valid
+/// [`ScopeName`], appears once, and has its parent in the table, except
+/// that the root and top-level scopes never need a parent entry.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(try_from = "TableJson", into = "TableJson")]
 pub struct ScopeTable {
-    pub scopes: Vec<Scope>,
+    scopes: Vec<Scope>,
+    index: HashMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableJson {
+    scopes: Vec<Scope>,
+}
+
+impl TryFrom<TableJson> for ScopeTable {
+    type Error = anyhow::Error;
+
+    fn try_from(json: TableJson) -> Result<Self> {
+        Self::new(json.scopes)
+    }
+}
+
+impl From<ScopeTable> for TableJson {
+    fn from(table: ScopeTable) -> Self {
+        Self {
+            scopes: table.scopes,
+        }
+    }
 }
 
 impl ScopeTable {
-    pub fn new(scopes: Vec<Scope>) -> Self {
-        Self { scopes }
+    /// Checks every scope as [`ScopeTable::insert`] would, in any order.
+    pub fn new(scopes: Vec<Scope>) -> Result<Self> {
+        let mut table = Self::default();
+        for scope in scopes {
+            ScopeName::parse(&scope.name)?;
+            if table.index.contains_key(&scope.name) {
+                bail!("🌐:{} is in the table twice", scope.name);
+            }
+            table.index.insert(scope.name.clone(), table.scopes.len());
+            table.scopes.push(scope);
+        }
+        if let Some(orphan) = table.scopes.iter().find(|s| table.is_orphan(&s.name)) {
+            bail!("🌐:{} has no parent in the table", orphan.name);
+        }
+        Ok(table)
     }
 
     /// Accepts either:
@@ -25,7 +66,7 @@ impl ScopeTable {
         // Fallback: bare array of scopes
         if json.is_array() {
             let scopes: Vec<Scope> = serde_json::from_value(json)?;
-            return Ok(Self { scopes });
+            return Self::new(scopes);
         }
         Err(anyhow!("expected {{\"scopes\": [...]}} or [...]"))
     }
@@ -35,15 +76,169 @@ impl ScopeTable {
         Ok(serde_json::to_value(self)?)
     }
 
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn into_scopes(self) -> Vec<Scope> {
+        self.scopes
+    }
+
     pub fn lookup(&self, scope_name: &str) -> Option<&Scope> {
-        self.scopes.iter().find(|s| s.name == scope_name)
+        self.index.get(scope_name).map(|&i| &self.scopes[i])
+    }
+
+    /// Names can't change through this, so the index stays good.
+    pub fn update(&mut self, scope_name: &str, update: impl FnOnce(&mut Scope)) -> bool {
+        let Some(&i) = self.index.get(scope_name) else {
+            return false;
+        };
+        let name = self.scopes[i].name.clone();
+        update(&mut self.scopes[i]);
+        self.scopes[i].name = name;
+        true
+    }
+
+    /// Add a scope. Fails on a bad or duplicate name, or if the parent
+    /// isn't in the table.
+    pub fn insert(&mut self, scope: Scope) -> Result<()> {
+        ScopeName::parse(&scope.name)?;
+        if self.index.contains_key(&scope.name) {
+            bail!("🌐:{} is already in the table", scope.name);
+        }
+        if self.is_orphan(&scope.name) {
+            bail!("🌐:{} has no parent in the table", scope.name);
+        }
+        self.index.insert(scope.name.clone(), self.scopes.len());
+        self.scopes.push(scope);
+        Ok(())
+    }
+
+    /// Remove a scope. Fails if that would orphan its children.
+    pub fn remove_scope(&mut self, scope_name: &str) -> Result<()> {
+        if let Some(child) = self.children(scope_name).first() {
+            bail!("🌐:{scope_name} still has 🌐:{} under it", child.name);
+        }
+        self.scopes.retain(|s| s.name != scope_name);
+        self.reindex();
+        Ok(())
+    }
+
+    /// The nearest scope above `scope_name` that's in the table.
+    pub fn parent(&self, scope_name: &str) -> Option<&Scope> {
+        let name = ScopeName::parse(scope_name).ok()?;
+        name.ancestors()
+            .iter()
+            .rev()
+            .find_map(|a| self.lookup(a.as_str()))
+    }
+
+    /// Scopes directly below `scope_name`, by name.
+    pub fn children(&self, scope_name: &str) -> Vec<&Scope> {
+        let mut out: Vec<&Scope> = self
+            .scopes
+            .iter()
+            .filter(|s| {
+                ScopeName::parse(&s.name)
+                    .ok()
+                    .and_then(|n| n.parent())
+                    .is_some_and(|p| p.as_str() == scope_name)
+            })
+            .collect();
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        out
+    }
+
+    /// Scopes above `scope_name` that are in the table, root first.
+    pub fn ancestors(&self, scope_name: &str) -> Vec<&Scope> {
+        let Ok(name) = ScopeName::parse(scope_name) else {
+            return Vec::new();
+        };
+        name.ancestors()
+            .iter()
+            .filter_map(|a| self.lookup(a.as_str()))
+            .collect()
+    }
+
+    /// Every scope below `scope_name`, depth first, by name.
+    pub fn descendants(&self, scope_name: &str) -> Vec<&Scope> {
+        let mut out = Vec::new();
+        for child in self.children(scope_name) {
+            out.push(child);
+            out.extend(self.descendants(&child.name));
+        }
+        out
+    }
+
+    /// `scope_name` itself, if we have it, then its descendants.
+    pub fn subtree(&self, scope_name: &str) -> impl Iterator<Item = &Scope> {
+        self.lookup(scope_name)
+            .into_iter()
+            .chain(self.descendants(scope_name))
+    }
+
+    /// Root and top-level scopes never count as orphans. Below that the
+    /// parent has to be in the table.
+    fn is_orphan(&self, scope_name: &str) -> bool {
+        match ScopeName::parse(scope_name).ok().and_then(|n| n.parent()) {
+            Some(parent) if !parent.is_root() => !self.index.contains_key(parent.as_str()),
+            _ => false,
+        }
+    }
+
+    fn reindex(&mut self) {
+        self.index = self
+            .scopes
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.clone(), i))
+            .collect();
     }
 
     pub fn to_string(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
+}
 
-    pub fn remove_scope(&mut self, scope_name: &str) {
-        self.scopes.retain(|s| s.name != scope_name);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(names: &[&str]) -> Result<ScopeTable> {
+        ScopeTable::new(names.iter().map(|n| Scope::new(n, "authority")).collect())
+    }
+
+    fn names<'a>(scopes: impl IntoIterator<Item = &'a Scope>) -> Vec<&'a str> {
+        scopes.into_iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn tree_queries() {
+        let t = table(&["", "a", "a.c", "a.b", "a.b.x", "z"]).unwrap();
+        assert_eq!(names(t.children("a")), ["a.b", "a.c"]);
+        assert_eq!(names(t.children("")), ["a", "z"]);
+        assert_eq!(names(t.ancestors("a.b.x")), ["", "a", "a.b"]);
+        assert_eq!(t.parent("a.b.x").unwrap().name, "a.b");
+        assert_eq!(names(t.descendants("a")), ["a.b", "a.b.x", "a.c"]);
+        assert_eq!(names(t.subtree("a.b")), ["a.b", "a.b.x"]);
+    }
+
+    #[test]
+    fn keeps_the_tree_whole() {
+        assert!(table(&["a.b"]).is_err());
+        assert!(table(&["a", "a"]).is_err());
+        assert!(table(&["A"]).is_err());
+
+        let mut t = table(&["a"]).unwrap();
+        assert!(t.insert(Scope::new("a.b.c", "mirror")).is_err());
+        t.insert(Scope::new("a.b", "mirror")).unwrap();
+        assert!(t.remove_scope("a").is_err());
+        t.remove_scope("a.b").unwrap();
+        t.remove_scope("a").unwrap();
+        assert!(t.lookup("a").is_none());
+
+        let json = table(&["a", "a.b"]).unwrap().to_json().unwrap();
+        let back = ScopeTable::from_json(json).unwrap();
+        assert_eq!(back.lookup("a.b").unwrap().name, "a.b");
     }
 }
@@ -0,0 +1,320 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::time::{GTClock, MICROMARKS_PER_MARK, MICROMARKS_PER_TURN};
+
+const MICROMARKS_PER_TURN_U64: u64 = MICROMARKS_PER_TURN as u64;
+
+/// A point in GT, in micromarks since the ledger epoch. This is what
+/// ⏱️ `context.at` and the 🟢🕑/🔴🕑 policy windows hold.
+///
+/// Written as `GT[turn.dd.dd@ddd]`: the turn, then the nine digits of
+/// micromarks into the turn grouped 2, 2 and 5. The last group drops
+/// trailing zeros down to three digits, so the epoch is `GT[0.00.00@000]`
+/// and `GT[3.14.15@92653]` is micromark 3_141_592_653.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct GtTime(u64);
+
+/// A span of GT in micromarks.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct GtDuration(u64);
+
+impl GtTime {
+    pub const EPOCH: GtTime = GtTime(0);
+
+    pub const fn from_micromarks(micromarks: u64) -> Self {
+        Self(micromarks)
+    }
+
+    pub const fn micromarks(self) -> u64 {
+        self.0
+    }
+
+    pub const fn turn(self) -> u64 {
+        self.0 / MICROMARKS_PER_TURN_U64
+    }
+
+    /// Whole marks since the epoch, the window [`crate::policy::rate`] counts in.
+    pub const fn mark(self) -> u64 {
+        self.0 / MICROMARKS_PER_MARK
+    }
+
+    /// Fails before `clock`'s epoch; GT doesn't go negative.
+    pub fn from_unix_ms(unix_ms: i128, clock: &GTClock) -> Result<Self> {
+        let delta_ms = unix_ms - clock.epoch_unix_ms;
+        if delta_ms < 0 {
+            bail!(
+                "{unix_ms} ms is before the GT epoch at {} ms",
+                clock.epoch_unix_ms
+            );
+        }
+        let micromarks = delta_ms * MICROMARKS_PER_TURN / super::time::SIDEREAL_MS;
+        u64::try_from(micromarks)
+            .map(Self)
+            .map_err(|_| anyhow!("{unix_ms} ms is past the end of GT"))
+    }
+
+    pub fn to_unix_ms(self, clock: &GTClock) -> i128 {
+        clock.time_at_micromarks(self.0 as i128)
+    }
+
+    /// Takes any RFC 3339 offset; sub-millisecond digits are dropped.
+    pub fn from_rfc3339(s: &str, clock: &GTClock) -> Result<Self> {
+        let when = DateTime::parse_from_rfc3339(s)?;
+        Self::from_unix_ms(when.timestamp_millis() as i128, clock)
+    }
+
+    /// UTC, to the millisecond.
+    pub fn to_rfc3339(self, clock: &GTClock) -> Result<String> {
+        let ms = i64::try_from(self.to_unix_ms(clock))?;
+        let when = DateTime::<Utc>::from_timestamp_millis(ms)
+            .ok_or_else(|| anyhow!("{self} is out of range for a UTC timestamp"))?;
+        Ok(when.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+
+    pub fn checked_add(self, d: GtDuration) -> Option<Self> {
+        self.0.checked_add(d.0).map(Self)
+    }
+
+    pub fn checked_sub(self, d: GtDuration) -> Option<Self> {
+        self.0.checked_sub(d.0).map(Self)
+    }
+
+    /// Time from `earlier` to here, `None` if `earlier` is later.
+    pub fn checked_since(self, earlier: GtTime) -> Option<GtDuration> {
+        self.0.checked_sub(earlier.0).map(GtDuration)
+    }
+}
+
+impl GtDuration {
+    pub const ZERO: GtDuration = GtDuration(0);
+
+    pub const fn from_micromarks(micromarks: u64) -> Self {
+        Self(micromarks)
+    }
+
+    pub const fn from_marks(marks: u64) -> Self {
+        Self(marks * MICROMARKS_PER_MARK)
+    }
+
+    pub const fn from_turns(turns: u64) -> Self {
+        Self(turns * MICROMARKS_PER_TURN_U64)
+    }
+
+    pub const fn micromarks(self) -> u64 {
+        self.0
+    }
+
+    /// Whole marks, rounded down.
+    pub const fn marks(self) -> u64 {
+        self.0 / MICROMARKS_PER_MARK
+    }
+}
+
+impl fmt::Display for GtTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let into = self.0 % MICROMARKS_PER_TURN_U64;
+        let (hi, lo, rest) = (into / 10_000_000, into / 100_000 % 100, into % 100_000);
+        let mut rest = format!("{rest:05}");
+        while rest.len() > 3 && rest.ends_with('0') {
+            rest.pop();
+        }
+        write!(f, "GT[{}.{hi:02}.{lo:02}@{rest}]", self.turn())
+    }
+}
+
+impl FromStr for GtTime {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bad = || anyhow!("{s:?} isn't GT[turn.dd.dd@ddd]");
+        let inner = s
+            .trim()
+            .strip_prefix("GT[")
+            .and_then(|r| r.strip_suffix(']'))
+            .ok_or_else(bad)?;
+        let (head, rest) = inner.split_once('@').ok_or_else(bad)?;
+        let mut parts = head.split('.');
+        let (Some(turn), Some(hi), Some(lo), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(bad());
+        };
+        let digits = |d: &str, min: usize, max: usize| -> Result<u64> {
+            if d.len() < min || d.len() > max || !d.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(bad());
+            }
+            Ok(d.parse()?)
+        };
+        let turn = digits(turn, 1, 20)?;
+        let into = digits(hi, 2, 2)? * 10_000_000
+            + digits(lo, 2, 2)? * 100_000
+            + digits(rest, 1, 5)? * 10u64.pow(5 - rest.len() as u32);
+        turn.checked_mul(MICROMARKS_PER_TURN_U64)
+            .and_then(|t| t.checked_add(into))
+            .map(Self)
+            .ok_or_else(|| anyhow!("{s:?} is past the end of GT"))
+    }
+}
+
+impl fmt::Display for GtDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (marks, rest) = (self.0 / MICROMARKS_PER_MARK, self.0 % MICROMARKS_PER_MARK);
+        if rest == 0 {
+            write!(f, "{marks} marks")
+        } else {
+            write!(f, "{marks}.{rest:06} marks")
+        }
+    }
+}
+
+impl From<u64> for GtTime {
+    fn from(micromarks: u64) -> Self {
+        Self(micromarks)
+    }
+}
+
+impl From<GtTime> for u64 {
+    fn from(t: GtTime) -> Self {
+        t.0
+    }
+}
+
+impl Add<GtDuration> for GtTime {
+    type Output = GtTime;
+
+    fn add(self, d: GtDuration) -> GtTime {
+        self.checked_add(d).expect("GT overflow")
+    }
+}
+
+impl AddAssign<GtDuration> for GtTime {
+    fn add_assign(&mut self, d: GtDuration) {
+        *self = *self + d;
+    }
+}
+
+impl Sub<GtDuration> for GtTime {
+    type Output = GtTime;
+
+    fn sub(self, d: GtDuration) -> GtTime {
+        self.checked_sub(d).expect("GT before the epoch")
+    }
+}
+
+impl SubAssign<GtDuration> for GtTime {
+    fn sub_assign(&mut self, d: GtDuration) {
+        *self = *self - d;
+    }
+}
+
+impl Sub for GtTime {
+    type Output = GtDuration;
+
+    fn sub(self, earlier: GtTime) -> GtDuration {
+        self.checked_since(earlier)
+            .expect("GT subtraction went negative")
+    }
+}
+
+impl Add for GtDuration {
+    type Output = GtDuration;
+
+    fn add(self, other: GtDuration) -> GtDuration {
+        GtDuration(self.0 + other.0)
+    }
+}
+
+impl Sub for GtDuration {
+    type Output = GtDuration;
+
+    fn sub(self, other: GtDuration) -> GtDuration {
+        GtDuration(self.0 - other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation_roundtrip() {
+        assert_eq!(GtTime::EPOCH.to_string(), "GT[0.00.00@000]");
+        let t = GtTime::from_micromarks(3_141_592_653);
+        assert_eq!(t.to_string(), "GT[3.14.15@92653]");
+        assert_eq!(
+            GtTime::from_micromarks(1_999_900_000).to_string(),
+            "GT[1.99.99@000]"
+        );
+        assert_eq!(
+            GtTime::from_micromarks(5_120_000).to_string(),
+            "GT[0.00.51@200]"
+        );
+        for s in ["GT[0.00.00@000]", "GT[3.14.15@92653]", "GT[0.00.51@200]"] {
+            assert_eq!(s.parse::<GtTime>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            "GT[0.00.51@2]".parse::<GtTime>().unwrap().micromarks(),
+            5_120_000
+        );
+        for bad in [
+            "0.00.00@000",
+            "GT[0.0.00@000]",
+            "GT[0.00.00]",
+            "GT[0.00.00@123456]",
+            "GT[a.00.00@0]",
+        ] {
+            assert!(bad.parse::<GtTime>().is_err(), "{bad:?} parsed");
+        }
+    }
+
+    #[test]
+    fn wall_clock_and_arithmetic() {
+        let clock = GTClock::new(1_752_941_587_614);
+        assert_eq!(
+            GtTime::from_rfc3339("2025-07-19T16:13:07.614Z", &clock).unwrap(),
+            GtTime::EPOCH
+        );
+        assert_eq!(
+            GtTime::EPOCH.to_rfc3339(&clock).unwrap(),
+            "2025-07-19T16:13:07.614Z"
+        );
+        assert!(GtTime::from_unix_ms(0, &clock).is_err());
+
+        let turn = GtTime::EPOCH + GtDuration::from_turns(1);
+        assert_eq!(
+            turn.to_unix_ms(&clock) - clock.epoch_unix_ms,
+            super::super::time::SIDEREAL_MS
+        );
+        assert_eq!(
+            GtTime::from_unix_ms(turn.to_unix_ms(&clock), &clock).unwrap(),
+            turn
+        );
+
+        let t = GtTime::from_micromarks(10) + GtDuration::from_marks(2);
+        assert_eq!(t.mark(), 2);
+        assert_eq!((t - GtTime::from_micromarks(10)).marks(), 2);
+        assert_eq!(
+            GtDuration::from_micromarks(1_500_000).to_string(),
+            "1.500000 marks"
+        );
+        assert!(
+            GtTime::EPOCH
+                .checked_sub(GtDuration::from_micromarks(1))
+                .is_none()
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod gt;
+pub mod skew;
 pub mod time;
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::gt::{GtDuration, GtTime};
+
+/// How far a claimed ⏱️ may sit from our own clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkewWindow {
+    /// Behind our clock, which covers time in transit.
+    pub behind: GtDuration,
+    /// Ahead of our clock.
+    pub ahead: GtDuration,
+}
+
+impl Default for SkewWindow {
+    /// What an usher takes for records it signs: a mark ahead, five behind.
+    fn default() -> Self {
+        Self {
+            behind: GtDuration::from_marks(5),
+            ahead: GtDuration::from_marks(1),
+        }
+    }
+}
+
+impl SkewWindow {
+    /// Wide enough that only a broken clock falls outside; used on
+    /// records other ushers signed.
+    pub fn plausible() -> Self {
+        Self {
+            behind: GtDuration::from_turns(1),
+            ahead: GtDuration::from_marks(10),
+        }
+    }
+
+    pub fn check(&self, at: GtTime, now: GtTime) -> Result<(), TimeIssue> {
+        if let Some(by) = now.checked_since(at).filter(|&by| by > self.behind) {
+            return Err(TimeIssue::Behind { at, now, by });
+        }
+        if let Some(by) = at.checked_since(now).filter(|&by| by > self.ahead) {
+            return Err(TimeIssue::Ahead { at, now, by });
+        }
+        Ok(())
+    }
+}
+
+/// Why a claimed ⏱️ can't be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeIssue {
+    /// Not after the ⏱️ of the scope's head; time only moves forward.
+    NotAfterHead { head_at: GtTime, at: GtTime },
+    /// Further behind our clock than the window allows.
+    Behind {
+        at: GtTime,
+        now: GtTime,
+        by: GtDuration,
+    },
+    /// Further ahead of our clock than the window allows.
+    Ahead {
+        at: GtTime,
+        now: GtTime,
+        by: GtDuration,
+    },
+}
+
+impl TimeIssue {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TimeIssue::NotAfterHead { .. } => "time_not_monotonic",
+            TimeIssue::Behind { .. } | TimeIssue::Ahead { .. } => "clock_skew",
+        }
+    }
+}
+
+/// ⏱️ has to come after the scope head's, if there is a head.
+pub fn check_after_head(at: GtTime, head_at: Option<GtTime>) -> Result<(), TimeIssue> {
+    match head_at {
+        Some(head_at) if at <= head_at => Err(TimeIssue::NotAfterHead { head_at, at }),
+        _ => Ok(()),
+    }
+}
+
+impl fmt::Display for TimeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeIssue::NotAfterHead { head_at, at } => {
+                write!(f, "⏱️ {at} is not after the head's ⏱️ {head_at}")
+            }
+            TimeIssue::Behind { at, now, by } => {
+                write!(f, "⏱️ {at} is {by} behind our {now}")
+            }
+            TimeIssue::Ahead { at, now, by } => {
+                write!(f, "⏱️ {at} is {by} ahead of our {now}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_and_head() {
+        let now = GtTime::from_micromarks(100_000_000);
+        let w = SkewWindow::default();
+        assert!(w.check(now, now).is_ok());
+        assert!(w.check(now - GtDuration::from_marks(5), now).is_ok());
+        assert!(matches!(
+            w.check(now - GtDuration::from_marks(6), now),
+            Err(TimeIssue::Behind { .. })
+        ));
+        assert!(matches!(
+            w.check(now + GtDuration::from_marks(2), now),
+            Err(TimeIssue::Ahead { .. })
+        ));
+        assert!(
+            SkewWindow::plausible()
+                .check(now + GtDuration::from_marks(2), now)
+                .is_ok()
+        );
+
+        assert!(check_after_head(now, None).is_ok());
+        assert!(check_after_head(now, Some(now)).is_err());
+        assert!(check_after_head(now + GtDuration::from_micromarks(1), Some(now)).is_ok());
+    }
+}
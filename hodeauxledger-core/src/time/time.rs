@@ -1,11 +1,17 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use anyhow::{Result, bail};
+
+use super::gt::GtTime;
+use crate::Rhex;
+use crate::record::{body::RecordBody, scope::ScopeGenesis};
+
 /// 1 sidereal day in milliseconds.
 pub const SIDEREAL_MS: i128 = 86_164_090;
 /// Micromarks per sidereal day (1e9).
 pub const MICROMARKS_PER_TURN: i128 = 1_000_000_000;
-
-pub const EPOCH_AT_UNIX_MS: i128 = 1756704877985;
+/// One mark is a million micromarks (1/1000 of a turn).
+pub const MICROMARKS_PER_MARK: u64 = 1_000_000;
 
 /// Clock that converts wall time to GT relative to a ledger epoch (in ms).
 #[derive(Clone, Copy, Debug)]
@@ -17,12 +23,25 @@ pub struct GTClock {
 impl GTClock {
     /// Create a clock with the epoch pulled from your genesis record (ms).
     pub fn new(epoch_unix_ms: i128) -> Self {
-        if epoch_unix_ms == 0 {
-            Self {
-                epoch_unix_ms: EPOCH_AT_UNIX_MS,
-            }
-        } else {
-            Self { epoch_unix_ms }
+        Self { epoch_unix_ms }
+    }
+
+    /// The clock the root 🌐 genesis starts. Only that record carries an
+    /// epoch, so anything else is an error.
+    pub fn from_genesis(rhex: &Rhex) -> Result<Self> {
+        if !rhex.intent.scope.is_empty() || rhex.intent.record_type != "scope:genesis" {
+            bail!(
+                "only the root scope:genesis sets the GT epoch, not 📄 {} in 🌐:{}",
+                rhex.intent.record_type,
+                rhex.intent.scope
+            );
+        }
+        match RecordBody::from_rhex(rhex)? {
+            RecordBody::ScopeGenesis(ScopeGenesis {
+                unix_epoch_ms: Some(ms),
+                ..
+            }) => Ok(Self::new(ms.into())),
+            _ => bail!("root scope:genesis has no unix_epoch_ms"),
         }
     }
 
@@ -33,8 +52,12 @@ impl GTClock {
 
     /// Current GT as **total micromarks since epoch** (can be negative before epoch).
     pub fn now_micromarks(&self) -> i128 {
-        let now_ms = current_unix_ms();
-        let delta_ms = now_ms - self.epoch_unix_ms;
+        self.micromarks_at(current_unix_ms())
+    }
+
+    /// GT at Unix time `unix_ms`, in micromarks since epoch.
+    pub fn micromarks_at(&self, unix_ms: i128) -> i128 {
+        let delta_ms = unix_ms - self.epoch_unix_ms;
         // Convert ms → micromarks: floor division with full precision in i128.
         delta_ms.saturating_mul(MICROMARKS_PER_TURN) / SIDEREAL_MS
     }
@@ -43,6 +66,11 @@ impl GTClock {
         self.now_micromarks() as u64
     }
 
+    /// Current GT as a [`GtTime`], clamped to the epoch.
+    pub fn now(&self) -> GtTime {
+        GtTime::from_micromarks(self.now_micromarks().max(0) as u64)
+    }
+
     /// Split into (turn, micromarks_into_turn).
     pub fn now_turn_and_offset(&self) -> (i128, i128) {
         let mm_total = self.now_micromarks();
@@ -57,7 +85,7 @@ impl GTClock {
 }
 
 /// Helper: current Unix time in **milliseconds** as i128.
-fn current_unix_ms() -> i128 {
+pub fn current_unix_ms() -> i128 {
     let dur = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("system clock before 1970");
@@ -80,7 +108,7 @@ mod tests {
 
     #[test]
     fn split_roundtrip() {
-        let clock = GTClock::new(0);
+        let clock = GTClock::new(1_752_941_587_614);
         let mm = clock.now_micromarks();
         let (turn, into) = clock.now_turn_and_offset();
         assert_eq!(turn * MICROMARKS_PER_TURN + into, mm);
@@ -1,3 +1,5 @@
+use crate::crypto::b64::from_base64_to_32;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RhexUrl {
     pub scheme: String,
@@ -24,6 +26,19 @@ impl RhexUrl {
         }
     }
 
+    /// The ⬇️🧬 this URL points at, or None if it names an alias.
+    pub fn hash(&self) -> Option<[u8; 32]> {
+        from_base64_to_32(&self.hash_alias).ok()
+    }
+
+    /// Same URL pointing at `hash_alias` instead, keeping @version and #field.
+    pub fn with_hash_alias(&self, hash_alias: &str) -> Self {
+        Self {
+            hash_alias: hash_alias.to_string(),
+            ..self.clone()
+        }
+    }
+
     pub fn to_string(&self) -> String {
         let mut out = format!("{}://{}/", self.scheme, self.scope);
         out.push_str(&self.hash_alias);
@@ -1,41 +1,72 @@
 use hodeauxledger_core::Alias;
-use rusqlite::{Connection, params};
+use hodeauxledger_core::RhexError;
+use rusqlite::{Connection, OptionalExtension, params};
 
-pub fn cache_alias(conn: &Connection, alias: &Alias) -> anyhow::Result<()> {
-    let mut stmt = conn.prepare("INSERT INTO aliases (name, scope, hash) VALUES (?1, ?2, ?3)")?;
+/// Granting a name that is already taken in the scope repoints it.
+pub fn cache_alias(conn: &Connection, alias: &Alias) -> Result<(), RhexError> {
+    let mut stmt =
+        conn.prepare("INSERT OR REPLACE INTO aliases (name, scope, hash) VALUES (?1, ?2, ?3)")?;
     stmt.execute(params![alias.name, alias.scope, alias.hash])?;
     Ok(())
 }
 
-pub fn retrieve_alias(conn: &Connection, name: &str) -> anyhow::Result<Alias> {
-    let mut stmt = conn.prepare("SELECT scope, hash FROM aliases WHERE name = ?1")?;
-    let mut rows = stmt.query(params![name])?;
-    let mut alias = Alias {
-        name: String::new(),
-        scope: String::new(),
-        hash: [0u8; 32],
-    };
-    if let Some(row) = rows.next()? {
-        alias.name = name.to_string();
-        alias.scope = row.get("scope")?;
-        alias.hash = row.get("hash")?;
+pub fn retrieve_alias(
+    conn: &Connection,
+    scope: &str,
+    name: &str,
+) -> Result<Option<Alias>, RhexError> {
+    let mut stmt = conn.prepare("SELECT hash FROM aliases WHERE name = ?1 AND scope = ?2")?;
+    let hash: Option<[u8; 32]> = stmt
+        .query_row(params![name, scope], |row| row.get("hash"))
+        .optional()?;
+    Ok(hash.map(|hash| Alias::new(name, scope, &hash)))
+}
+
+/// Aliases in `scope` pointing at `hash`, by name.
+pub fn retrieve_aliases_for_hash(
+    conn: &Connection,
+    scope: &str,
+    hash: &[u8; 32],
+) -> Result<Vec<Alias>, RhexError> {
+    let mut stmt =
+        conn.prepare("SELECT name FROM aliases WHERE scope = ?1 AND hash = ?2 ORDER BY name")?;
+    let names = stmt.query_map(params![scope, hash], |row| row.get::<_, String>("name"))?;
+    let mut out = Vec::new();
+    for name in names {
+        out.push(Alias::new(&name?, scope, hash));
     }
-    Ok(alias)
+    Ok(out)
 }
 
-pub fn evict_alias(conn: &Connection, name: &str, scope: &str) -> anyhow::Result<()> {
+pub fn retrieve_scope_aliases(conn: &Connection, scope: &str) -> Result<Vec<Alias>, RhexError> {
+    let mut stmt = conn.prepare("SELECT name, hash FROM aliases WHERE scope = ?1 ORDER BY name")?;
+    let rows = stmt.query_map(params![scope], |row| {
+        Ok((
+            row.get::<_, String>("name")?,
+            row.get::<_, [u8; 32]>("hash")?,
+        ))
+    })?;
+    let mut out = Vec::new();
+    for row in rows {
+        let (name, hash) = row?;
+        out.push(Alias::new(&name, scope, &hash));
+    }
+    Ok(out)
+}
+
+pub fn evict_alias(conn: &Connection, name: &str, scope: &str) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM aliases WHERE name = ?1 AND scope = ?2")?;
     stmt.execute(params![name, scope])?;
     Ok(())
 }
 
-pub fn flush_aliases(conn: &Connection) -> anyhow::Result<()> {
+pub fn flush_aliases(conn: &Connection) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM aliases")?;
     stmt.execute([])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS aliases (
             name TEXT,
@@ -45,5 +76,40 @@ pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
         )",
         [],
     )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS aliases_by_hash ON aliases (scope, hash)",
+        [],
+    )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_name_in_two_scopes() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        cache_alias(&conn, &Alias::new("current", "a", &[1u8; 32])).unwrap();
+        cache_alias(&conn, &Alias::new("current", "b", &[2u8; 32])).unwrap();
+
+        assert_eq!(
+            retrieve_alias(&conn, "a", "current").unwrap().unwrap().hash,
+            [1u8; 32]
+        );
+        assert_eq!(
+            retrieve_alias(&conn, "b", "current").unwrap().unwrap().hash,
+            [2u8; 32]
+        );
+
+        evict_alias(&conn, "current", "a").unwrap();
+        assert!(retrieve_alias(&conn, "a", "current").unwrap().is_none());
+        assert_eq!(
+            retrieve_aliases_for_hash(&conn, "b", &[2u8; 32])
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
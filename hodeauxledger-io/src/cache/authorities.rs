@@ -1,12 +1,11 @@
+use hodeauxledger_core::RhexError;
 use hodeauxledger_core::scope::authority::Authority;
 use rusqlite::{Connection, params};
 
-pub fn retrieve_authorities(
-    conn: &Connection,
-    scope: &str,
-) -> Result<Vec<Authority>, anyhow::Error> {
-    let mut stmt = conn
-        .prepare("SELECT name,host,port,public_key,priority FROM authorities WHERE scope = ?1")?;
+pub fn retrieve_authorities(conn: &Connection, scope: &str) -> Result<Vec<Authority>, RhexError> {
+    let mut stmt = conn.prepare(
+        "SELECT name,host,port,proto,public_key,priority FROM authorities WHERE scope = ?1",
+    )?;
     let mut rows = stmt.query(params![scope])?;
     let mut authorities = Vec::new();
     while let Some(row) = rows.next()? {
@@ -27,16 +26,16 @@ pub fn retrieve_authorities(
         authorities.push(authority);
     }
 
-    Ok(Vec::new())
+    Ok(authorities)
 }
 
 pub fn cache_authority(
     conn: &Connection,
     scope: &str,
     authorities: &[Authority],
-) -> Result<(), anyhow::Error> {
+) -> Result<(), RhexError> {
     let mut stmt = conn.prepare(
-        "INSERT INTO authorities (scope, name, host, port, proto, public_key, priority) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        "INSERT OR REPLACE INTO authorities (scope, name, host, port, proto, public_key, priority) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
     )?;
     for authority in authorities {
         stmt.execute(params![
@@ -52,19 +51,19 @@ pub fn cache_authority(
     Ok(())
 }
 
-pub fn evict_authority(conn: &Connection, scope: &str, pk: &[u8; 32]) -> Result<(), anyhow::Error> {
+pub fn evict_authority(conn: &Connection, scope: &str, pk: &[u8; 32]) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM authorities WHERE scope = ?1 AND public_key = ?2")?;
     stmt.execute(params![scope, pk])?;
     Ok(())
 }
 
-pub fn flush_authorities(conn: &Connection, scope: &str) -> Result<(), anyhow::Error> {
+pub fn flush_authorities(conn: &Connection, scope: &str) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM authorities WHERE scope = ?1")?;
     stmt.execute(params![scope])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS authorities (
             scope TEXT,
@@ -0,0 +1,64 @@
+use hodeauxledger_core::RhexError;
+use rusqlite::{Connection, OptionalExtension, params};
+
+/// ⬇️🧬 of a banned record and of the record:ban that banned it.
+pub type Ban = ([u8; 32], [u8; 32]);
+
+/// Remember that `target` in `scope` was banned by the record:ban `ban_hash`.
+pub fn cache_ban(
+    conn: &Connection,
+    scope: &str,
+    target: &[u8; 32],
+    ban_hash: &[u8; 32],
+    at: u64,
+) -> Result<(), RhexError> {
+    let mut stmt = conn.prepare(
+        "INSERT OR IGNORE INTO bans (target, scope, ban_hash, at) VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    stmt.execute(params![target, scope, ban_hash, at as i64])?;
+    Ok(())
+}
+
+/// ⬇️🧬 of the record:ban for `target`, if it's banned.
+pub fn retrieve_ban(conn: &Connection, target: &[u8; 32]) -> Result<Option<[u8; 32]>, RhexError> {
+    let mut stmt = conn.prepare("SELECT ban_hash FROM bans WHERE target = ?1")?;
+    Ok(stmt
+        .query_row(params![target], |row| row.get("ban_hash"))
+        .optional()?)
+}
+
+/// (target, ban) pairs for `scope`, oldest ban first.
+pub fn retrieve_scope_bans(conn: &Connection, scope: &str) -> Result<Vec<Ban>, RhexError> {
+    let mut stmt =
+        conn.prepare("SELECT target, ban_hash FROM bans WHERE scope = ?1 ORDER BY at")?;
+    let rows = stmt.query_map(params![scope], |row| {
+        Ok((
+            row.get::<_, [u8; 32]>("target")?,
+            row.get::<_, [u8; 32]>("ban_hash")?,
+        ))
+    })?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
+pub fn flush_bans(conn: &Connection, scope: &str) -> Result<(), RhexError> {
+    let mut stmt = conn.prepare("DELETE FROM bans WHERE scope = ?1")?;
+    stmt.execute(params![scope])?;
+    Ok(())
+}
+
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS bans (
+            target BLOB PRIMARY KEY,
+            scope TEXT,
+            ban_hash BLOB,
+            at INTEGER
+        )",
+        [],
+    )?;
+    Ok(())
+}
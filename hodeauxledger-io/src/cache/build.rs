@@ -1,6 +1,7 @@
 use crate::cache::{self, cache::Cache};
+use hodeauxledger_core::RhexError;
 
-pub fn build_cache_db(path: &str) -> Result<(), anyhow::Error> {
+pub fn build_cache_db(path: &str) -> Result<(), RhexError> {
     let cache = Cache::connect(path)?;
     cache::authorities::build_table(&cache.conn)?;
     println!("built authorities table");
@@ -23,5 +24,11 @@ pub fn build_cache_db(path: &str) -> Result<(), anyhow::Error> {
     println!("built aliases table");
     cache::rules::build_table(&cache.conn)?;
     println!("built rules table");
+    cache::rate_limits::build_table(&cache.conn)?;
+    println!("built rate_limits table");
+    cache::bans::build_table(&cache.conn)?;
+    println!("built bans table");
+    cache::epoch::build_table(&cache.conn)?;
+    println!("built gt_epoch table");
     Ok(())
 }
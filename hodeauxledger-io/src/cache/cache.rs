@@ -1,5 +1,6 @@
 use crate::cache::key;
 use hodeauxledger_core::Key;
+use hodeauxledger_core::RhexError;
 use rusqlite::{Connection, params};
 
 #[derive(Debug)]
@@ -13,7 +14,7 @@ impl Cache {
         Self { conn }
     }
 
-    pub fn connect(path: &str) -> anyhow::Result<Self> {
+    pub fn connect(path: &str) -> Result<Self, RhexError> {
         let path = if path.is_empty() {
             "./data/cache.sqlite"
         } else {
@@ -23,47 +24,33 @@ impl Cache {
         Ok(Self { conn })
     }
 
-    pub fn cache_key(&self, key: &Key, scope: &str) -> anyhow::Result<()> {
+    pub fn cache_key(&self, key: &Key, scope: &str) -> Result<(), RhexError> {
         key::cache_key(&self.conn, scope, key)
     }
 
-    pub fn evict_key(&self, key: &[u8; 32], scope: &str) -> anyhow::Result<()> {
-        let mut stmt = self
-            .conn
-            .prepare("DELETE FROM keys WHERE key = ?1 AND scope = ?2")?;
-        stmt.execute(params![key, scope])?;
-        Ok(())
+    pub fn evict_key(&self, key: &[u8; 32], scope: &str) -> Result<(), RhexError> {
+        key::evict_key(&self.conn, scope, key)
     }
 
-    pub fn retrieve_key(&self, key: &[u8; 32], scope: &str) -> anyhow::Result<(String, u64)> {
-        let mut stmt = self.conn.prepare(
-            "SELECT roles, expiry
-                 FROM keys
-                 WHERE key = ?1 AND scope = ?2 LIMIT 1",
-        )?;
-        let mut rows = stmt.query(params![key, scope])?;
-        let mut roles = String::new();
-        let mut expiry: u64 = 0;
-        if let Some(row) = rows.next()? {
-            roles = row.get("roles")?;
-            expiry = row.get("expiry")?;
-        };
-        Ok((roles, expiry))
+    pub fn retrieve_key(&self, key: &[u8; 32], scope: &str) -> Result<Option<Key>, RhexError> {
+        key::retrieve_key(&self.conn, scope, key)
     }
 
-    pub fn flush_all_keys(&self) -> anyhow::Result<()> {
-        let mut stmt = self.conn.prepare("DELETE FROM keys")?;
+    pub fn flush_all_keys(&self) -> Result<(), RhexError> {
+        let mut stmt = self.conn.prepare("DELETE FROM public_keys")?;
         stmt.execute([])?;
         Ok(())
     }
 
-    pub fn flush_scope_keys(&self, scope: &str) -> anyhow::Result<()> {
-        let mut stmt = self.conn.prepare("DELETE FROM keys WHERE scope = ?1")?;
+    pub fn flush_scope_keys(&self, scope: &str) -> Result<(), RhexError> {
+        let mut stmt = self
+            .conn
+            .prepare("DELETE FROM public_keys WHERE scope = ?1")?;
         stmt.execute(params![scope])?;
         Ok(())
     }
 
-    pub fn flush_everything(&self) -> anyhow::Result<()> {
+    pub fn flush_everything(&self) -> Result<(), RhexError> {
         let mut stmt = self.conn.prepare("DELETE FROM public_keys")?;
         stmt.execute([])?;
         let mut stmt = self.conn.prepare("DELETE FROM policies")?;
@@ -76,54 +63,29 @@ impl Cache {
         stmt.execute([])?;
         let mut stmt = self.conn.prepare("DELETE FROM aliases")?;
         stmt.execute([])?;
+        let mut stmt = self.conn.prepare("DELETE FROM bans")?;
+        stmt.execute([])?;
+        let mut stmt = self.conn.prepare("DELETE FROM rules")?;
+        stmt.execute([])?;
+        let mut stmt = self.conn.prepare("DELETE FROM gt_epoch")?;
+        stmt.execute([])?;
 
         Ok(())
     }
 
-    pub fn delete_db(&self) -> anyhow::Result<()> {
+    pub fn delete_db(&self) -> Result<(), RhexError> {
         std::fs::remove_file("./data/cache.sqlite")?;
         Ok(())
     }
 }
 
-pub fn store_policy(
-    conn: &Connection,
-    policy: &serde_json::Value,
-    scope: &str,
-    expiry: &u64,
-) -> anyhow::Result<()> {
-    let policy_string = serde_json::to_string(policy)?;
-
-    let mut stmt = conn.prepare("DELETE FROM policies WHERE scope = ?1")?;
-    stmt.execute(params![scope])?;
-
-    let mut stmt =
-        conn.prepare("INSERT INTO policies (policy, scope, expiry) VALUES (?1, ?2, ?3)")?;
-    stmt.execute(params![policy_string, scope, expiry])?;
-    Ok(())
-}
-
-pub fn retrieve_policy(conn: &Connection, scope: &str) -> anyhow::Result<serde_json::Value> {
-    let mut stmt = conn.prepare(
-        "SELECT policy
-         FROM policies
-         WHERE scope = ?1 ORDER BY effective DESC LIMIT 1",
-    )?;
-    let mut rows = stmt.query(params![scope])?;
-    let mut policy_string = String::new();
-    if let Some(row) = rows.next()? {
-        policy_string = row.get("policy")?;
-    }
-    Ok(serde_json::from_str(&policy_string)?)
-}
-
-pub fn revoke_policy(conn: &Connection, scope: &str) -> anyhow::Result<()> {
+pub fn revoke_policy(conn: &Connection, scope: &str) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM policies WHERE scope = ?1")?;
     stmt.execute(params![scope])?;
     Ok(())
 }
 
-pub fn flush_policies(conn: &Connection) -> anyhow::Result<()> {
+pub fn flush_policies(conn: &Connection) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM policies")?;
     stmt.execute([])?;
     Ok(())
@@ -0,0 +1,44 @@
+use hodeauxledger_core::RhexError;
+use rusqlite::{Connection, OptionalExtension, params};
+
+/// Remember the GT epoch and the root genesis it came from. There's only
+/// ever one row; a different epoch than the one stored is refused.
+pub fn cache_epoch(conn: &Connection, unix_ms: i128, genesis: &[u8; 32]) -> Result<(), RhexError> {
+    if let Some((stored, _)) = retrieve_epoch(conn)? {
+        if stored != unix_ms {
+            return Err(RhexError::Cache(format!(
+                "GT epoch is already {stored} ms, not {unix_ms} ms"
+            )));
+        }
+        return Ok(());
+    }
+    let mut stmt =
+        conn.prepare("INSERT INTO gt_epoch (id, unix_ms, genesis) VALUES (0, ?1, ?2)")?;
+    stmt.execute(params![unix_ms as i64, genesis])?;
+    Ok(())
+}
+
+/// The stored epoch in Unix ms and the ⬇️🧬 of its genesis.
+pub fn retrieve_epoch(conn: &Connection) -> Result<Option<(i128, [u8; 32])>, RhexError> {
+    let mut stmt = conn.prepare("SELECT unix_ms, genesis FROM gt_epoch WHERE id = 0")?;
+    Ok(stmt
+        .query_row([], |row| {
+            Ok((
+                row.get::<_, i64>("unix_ms")? as i128,
+                row.get::<_, [u8; 32]>("genesis")?,
+            ))
+        })
+        .optional()?)
+}
+
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS gt_epoch (
+            id INTEGER PRIMARY KEY CHECK (id = 0),
+            unix_ms INTEGER NOT NULL,
+            genesis BLOB NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
@@ -1,19 +1,160 @@
-use hodeauxledger_core::Key;
-use rusqlite::{Connection, params};
+use ed25519_dalek::VerifyingKey;
+use hodeauxledger_core::RhexError;
+use hodeauxledger_core::{
+    Key,
+    key::revocation::{Revocation, RevokeReason},
+};
+use rusqlite::{Connection, OptionalExtension, params};
 
-pub fn cache_key(conn: &Connection, scope: &str, key: &Key) -> anyhow::Result<()> {
-    let mut stmt = conn.prepare("INSERT INTO keys (scope, roles, pk, effective_micromark, expires_micromark) VALUES (?1, ?2, ?3, ?4, ?5)")?;
+/// Cache a key grant for a scope, replacing any earlier grant of the same
+/// key there. Granting a key again lifts an earlier revocation, unless the
+/// key was compromised.
+pub fn cache_key(conn: &Connection, scope: &str, key: &Key) -> Result<(), RhexError> {
+    cache_grant(conn, scope, key, None)
+}
+
+/// Like [`cache_key`], remembering the ⬇️🧬 of the key:grant so a
+/// key:revoke can name it.
+pub fn cache_grant(
+    conn: &Connection,
+    scope: &str,
+    key: &Key,
+    grant_ref: Option<&[u8; 32]>,
+) -> Result<(), RhexError> {
+    let pk = key
+        .pk
+        .ok_or_else(|| RhexError::encoding("key has no public key"))?;
+    let revoked = key.revoked.as_ref();
+    let mut stmt = conn.prepare(
+        "INSERT INTO public_keys
+            (scope, roles, public_key, effective_micromark, expires_micromark,
+             grant_ref, revoked_reason, revoked_micromark, compromised_micromark, revoke_ref)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+         ON CONFLICT (scope, public_key) DO UPDATE SET
+            roles = excluded.roles,
+            effective_micromark = excluded.effective_micromark,
+            expires_micromark = excluded.expires_micromark,
+            grant_ref = excluded.grant_ref,
+            revoked_reason = IIF(revoked_reason = 'compromised',
+                revoked_reason, excluded.revoked_reason),
+            revoked_micromark = IIF(revoked_reason = 'compromised',
+                revoked_micromark, excluded.revoked_micromark),
+            compromised_micromark = IIF(revoked_reason = 'compromised',
+                compromised_micromark, excluded.compromised_micromark),
+            revoke_ref = IIF(revoked_reason = 'compromised',
+                revoke_ref, excluded.revoke_ref)",
+    )?;
     stmt.execute(params![
         scope,
-        key.roles.as_ref().unwrap().join(","),
-        key.pk.unwrap().to_bytes(),
+        key.roles.clone().unwrap_or_default().join(","),
+        pk.to_bytes(),
         key.effective_micromark,
-        key.expires_micromark
+        key.expires_micromark,
+        grant_ref,
+        revoked.map(|r| r.reason.as_str()),
+        revoked.map(|r| r.effective_micromark),
+        revoked.and_then(|r| r.compromised_micromark),
+        revoked.and_then(|r| r.revoke_ref),
     ])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
+/// Mark `public_key` revoked in `scope`. The row is kept, so the key's
+/// grant and revocation stay on record; a key that was never granted gets
+/// a row with no 🥐.
+pub fn revoke_key(
+    conn: &Connection,
+    scope: &str,
+    public_key: &[u8; 32],
+    revocation: &Revocation,
+) -> Result<(), RhexError> {
+    conn.execute(
+        "INSERT INTO public_keys
+            (scope, roles, public_key, revoked_reason, revoked_micromark,
+             compromised_micromark, revoke_ref)
+         VALUES (?1, '', ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (scope, public_key) DO UPDATE SET
+            revoked_reason = excluded.revoked_reason,
+            revoked_micromark = excluded.revoked_micromark,
+            compromised_micromark = excluded.compromised_micromark,
+            revoke_ref = excluded.revoke_ref",
+        params![
+            scope,
+            public_key,
+            revocation.reason.as_str(),
+            revocation.effective_micromark,
+            revocation.compromised_micromark,
+            revocation.revoke_ref,
+        ],
+    )?;
+    Ok(())
+}
+
+/// The grant of `public_key` in `scope`, if there is one, revoked or not.
+pub fn retrieve_key(
+    conn: &Connection,
+    scope: &str,
+    public_key: &[u8; 32],
+) -> Result<Option<Key>, RhexError> {
+    let mut stmt = conn.prepare(
+        "SELECT roles, effective_micromark, expires_micromark,
+                revoked_reason, revoked_micromark, compromised_micromark, revoke_ref
+         FROM public_keys
+         WHERE scope = ?1 AND public_key = ?2",
+    )?;
+    let mut rows = stmt.query(params![scope, public_key])?;
+    let Some(row) = rows.next()? else {
+        return Ok(None);
+    };
+    let roles: String = row.get("roles")?;
+    let mut key = Key::new();
+    key.set_pub_key(VerifyingKey::from_bytes(public_key).map_err(RhexError::decoding)?);
+    key.roles = Some(
+        roles
+            .split(',')
+            .filter(|r| !r.is_empty())
+            .map(|r| r.to_string())
+            .collect(),
+    );
+    key.effective_micromark = row.get("effective_micromark")?;
+    key.expires_micromark = row.get("expires_micromark")?;
+    if let Some(reason) = row.get::<_, Option<String>>("revoked_reason")? {
+        key.revoked = Some(Revocation {
+            reason: RevokeReason::parse(&reason)
+                .ok_or_else(|| RhexError::decoding(format!("unknown revoke reason {reason}")))?,
+            effective_micromark: row.get::<_, Option<u64>>("revoked_micromark")?.unwrap_or(0),
+            compromised_micromark: row.get("compromised_micromark")?,
+            revoke_ref: row.get("revoke_ref")?,
+        });
+    }
+    Ok(Some(key))
+}
+
+/// ⬇️🧬 of the key:grant behind `public_key`'s grant in `scope`.
+pub fn retrieve_grant_ref(
+    conn: &Connection,
+    scope: &str,
+    public_key: &[u8; 32],
+) -> Result<Option<[u8; 32]>, RhexError> {
+    let grant_ref = conn
+        .query_row(
+            "SELECT grant_ref FROM public_keys WHERE scope = ?1 AND public_key = ?2",
+            params![scope, public_key],
+            |row| row.get::<_, Option<[u8; 32]>>(0),
+        )
+        .optional()?;
+    Ok(grant_ref.flatten())
+}
+
+pub fn evict_key(conn: &Connection, scope: &str, public_key: &[u8; 32]) -> Result<(), RhexError> {
+    conn.execute(
+        "DELETE FROM public_keys WHERE scope = ?1 AND public_key = ?2",
+        params![scope, public_key],
+    )?;
+    Ok(())
+}
+
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS public_keys (
             scope TEXT,
@@ -21,9 +162,108 @@ pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
             public_key BLOB,
             effective_micromark INTEGER,
             expires_micromark INTEGER,
+            grant_ref BLOB,
+            revoked_reason TEXT,
+            revoked_micromark INTEGER,
+            compromised_micromark INTEGER,
+            revoke_ref BLOB,
             PRIMARY KEY (scope, public_key)
         )",
         [],
     )?;
+    // Caches built before keys could be revoked.
+    for (column, decl) in [
+        ("grant_ref", "BLOB"),
+        ("revoked_reason", "TEXT"),
+        ("revoked_micromark", "INTEGER"),
+        ("compromised_micromark", "INTEGER"),
+        ("revoke_ref", "BLOB"),
+    ] {
+        let exists = conn
+            .prepare("SELECT 1 FROM pragma_table_info('public_keys') WHERE name = ?1")?
+            .exists(params![column])?;
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE public_keys ADD COLUMN {column} {decl}"),
+                [],
+            )?;
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn granted(conn: &Connection) -> ([u8; 32], Key) {
+        let mut key = Key::generate();
+        key.roles = Some(vec!["👑".to_string()]);
+        key.effective_micromark = Some(10);
+        let pk = key.pk.unwrap().to_bytes();
+        cache_grant(conn, "core", &key, Some(&[1u8; 32])).unwrap();
+        (pk, key)
+    }
+
+    #[test]
+    fn revocation_roundtrip() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        let (pk, _) = granted(&conn);
+        assert_eq!(
+            retrieve_key(&conn, "core", &pk).unwrap().unwrap().revoked,
+            None
+        );
+
+        let revocation = Revocation {
+            reason: RevokeReason::Compromised,
+            effective_micromark: 500,
+            compromised_micromark: Some(300),
+            revoke_ref: Some([2u8; 32]),
+        };
+        revoke_key(&conn, "core", &pk, &revocation).unwrap();
+        let key = retrieve_key(&conn, "core", &pk).unwrap().unwrap();
+        assert_eq!(key.revoked, Some(revocation));
+        assert_eq!(key.roles, Some(vec!["👑".to_string()]));
+        assert_eq!(key.effective_micromark, Some(10));
+        assert_eq!(
+            retrieve_grant_ref(&conn, "core", &pk).unwrap(),
+            Some([1u8; 32])
+        );
+
+        let stray = [9u8; 32];
+        let retired = Revocation {
+            reason: RevokeReason::Retired,
+            effective_micromark: 700,
+            compromised_micromark: None,
+            revoke_ref: None,
+        };
+        revoke_key(&conn, "core", &stray, &retired).unwrap();
+        assert_eq!(
+            retrieve_key(&conn, "core", &stray)
+                .unwrap()
+                .unwrap()
+                .revoked,
+            Some(retired)
+        );
+    }
+
+    #[test]
+    fn regrant_keeps_only_a_compromise() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        let (pk, key) = granted(&conn);
+        let compromise = Revocation {
+            reason: RevokeReason::Compromised,
+            effective_micromark: 500,
+            compromised_micromark: Some(300),
+            revoke_ref: Some([2u8; 32]),
+        };
+        revoke_key(&conn, "core", &pk, &compromise).unwrap();
+        cache_grant(&conn, "core", &key, Some(&[3u8; 32])).unwrap();
+        assert_eq!(
+            retrieve_key(&conn, "core", &pk).unwrap().unwrap().revoked,
+            Some(compromise)
+        );
+    }
+}
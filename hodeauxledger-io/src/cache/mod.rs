@@ -1,9 +1,12 @@
 pub mod aliases;
 pub mod authorities;
+pub mod bans;
 pub mod build;
 pub mod cache;
+pub mod epoch;
 pub mod key;
 pub mod policies;
+pub mod rate_limits;
 pub mod rhex;
 pub mod rules;
 pub mod scopes;
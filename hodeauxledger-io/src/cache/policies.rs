@@ -1,41 +1,44 @@
+use hodeauxledger_core::RhexError;
 use hodeauxledger_core::policy::{default::Default, policy::Policy};
 
-use rusqlite::{Connection, params}; // your Default struct
+use rusqlite::{Connection, OptionalExtension, Row, params};
 
+/// Keep a 📜 set by the `policy:set` (or genesis) record `current_hash`,
+/// appended at `set_at`. Every version is kept; a policy takes effect at
+/// its 🟢🕑, but never before it was set.
 pub fn cache_policy(
     conn: &Connection,
     scope: &str,
     policy: &Policy,
     current_hash: &[u8; 32],
-) -> Result<(), anyhow::Error> {
+    set_at: u64,
+) -> Result<(), RhexError> {
     // clone Options from &Policy safely
     let note = policy.note.clone().unwrap_or_default();
 
-    let defaults = policy.defaults.clone().unwrap_or(Default {
-        rate_per_mark: 80,
-        quorum_k: 1,
-        roles: vec!["👑".to_string()],
-        quorum_roles: vec!["👑".to_string()],
-    });
-
-    // DELETE must be parameterized with the scope
-    conn.execute("DELETE FROM policies WHERE scope = ?1", params![scope])?;
+    // No 🧱 is stored as NULL so the scope inherits its parent's.
+    let defaults = policy.defaults.as_ref();
 
     // Prepare values with proper types
-    let default_roles = defaults.roles.join(",");
-    let default_quorum_roles = defaults.quorum_roles.join(",");
-    let default_rate = defaults.rate_per_mark as i64;
-    let default_quorum_k = defaults.quorum_k as i64;
+    let default_roles = defaults.map(|d| d.roles.join(","));
+    let default_quorum_roles = defaults.map(|d| d.quorum_roles.join(","));
+    let default_rate = defaults.map(|d| d.rate_per_mark as i64);
+    let default_quorum_k = defaults.map(|d| d.quorum_k as i64);
 
     let quorum_ttl = policy.quorum_ttl.unwrap_or(0) as i64;
-    let effective_micromarks = policy.effective_micromark.unwrap_or(0) as i64;
+    let effective_micromarks = policy.effective_micromark.unwrap_or(0).max(set_at) as i64;
     let expires_micromarks = policy.expiration_micromark.unwrap_or(0) as i64;
+    let max_record_bytes = policy.max_record_bytes.map(|n| n as i64);
+    let max_data_bytes = policy.max_data_bytes.map(|n| n as i64);
+    let inherit = policy.inherit as i64;
+    let policy_json = serde_json::to_string(&policy.to_canonical())?;
 
     conn.execute(
-        "INSERT INTO policies (
+        "INSERT OR REPLACE INTO policies (
             scope, note, default_rate, default_roles, default_quorum_k, default_quorum_roles,
-            quorum_ttl, effective_micromarks, expires_micromarks, current_hash
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            quorum_ttl, effective_micromarks, expires_micromarks, current_hash,
+            max_record_bytes, max_data_bytes, inherit, policy, set_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         params![
             scope,
             note,
@@ -47,55 +50,116 @@ pub fn cache_policy(
             effective_micromarks,
             expires_micromarks,
             current_hash,
+            max_record_bytes,
+            max_data_bytes,
+            inherit,
+            policy_json,
+            set_at as i64,
         ],
     )?;
 
     Ok(())
 }
 
-pub fn retrieve_policy(conn: &Connection, scope: &str) -> Result<Policy, anyhow::Error> {
-    let mut stmt = conn.prepare(
-        "SELECT note, default_rate, default_roles, default_quorum_k, default_quorum_roles,
-                quorum_ttl, effective_micromarks, expires_micromarks, current_hash
-         FROM policies
-         WHERE scope = ?1",
-    )?;
-    let mut rows = stmt.query(params![scope])?;
-    let mut policy = Policy {
-        note: None,
-        defaults: None,
-        quorum_ttl: None,
-        effective_micromark: None,
-        expiration_micromark: None,
-        scope: scope.to_string(),
-        rules: Vec::new(),
-    };
-    if let Some(row) = rows.next()? {
-        policy.note = row.get::<_, String>("note").ok();
+const COLUMNS: &str = "note, default_rate, default_roles, default_quorum_k, default_quorum_roles,
+                quorum_ttl, effective_micromarks, expires_micromarks, current_hash,
+                max_record_bytes, max_data_bytes, inherit, policy";
+
+fn row_to_policy(row: &Row, scope: &str) -> Result<Policy, RhexError> {
+    // Whole 📜 as set, rules included.
+    if let Some(json) = row.get::<_, Option<String>>("policy")? {
+        let mut policy =
+            Policy::parse(&serde_json::from_str(&json)?).map_err(RhexError::decoding)?;
+        policy.scope = scope.to_string();
+        return Ok(policy);
+    }
+
+    // Rows cached before the 📜 itself was kept; rules live in the rules
+    // table.
+    let mut policy = Policy::new(scope, Vec::new());
+    policy.note = row.get::<_, Option<String>>("note")?;
+    if let Some(roles) = row.get::<_, Option<String>>("default_roles")? {
         policy.defaults = Some(Default {
-            rate_per_mark: row.get::<_, i64>("default_rate")? as u64,
-            roles: row
-                .get::<_, String>("default_roles")?
-                .split(",")
-                .map(|s| s.to_string())
-                .collect(),
-            quorum_k: row.get::<_, i64>("default_quorum_k")? as u8,
+            rate_per_mark: row.get::<_, Option<i64>>("default_rate")?.unwrap_or(0) as u64,
+            roles: roles.split(",").map(|s| s.to_string()).collect(),
+            quorum_k: row.get::<_, Option<i64>>("default_quorum_k")?.unwrap_or(0) as u8,
             quorum_roles: row
-                .get::<_, String>("default_quorum_roles")?
+                .get::<_, Option<String>>("default_quorum_roles")?
+                .unwrap_or_default()
                 .split(",")
                 .map(|s| s.to_string())
                 .collect(),
         });
-        policy.quorum_ttl = row.get::<_, i64>("quorum_ttl")?.try_into().ok();
-        policy.effective_micromark = row.get::<_, i64>("effective_micromarks")?.try_into().ok();
-        policy.expiration_micromark = row.get::<_, i64>("expires_micromarks")?.try_into().ok();
-        // current_hash is not part of the Policy struct, but it's in the table
-        // let current_hash: Vec<u8> = row.get("current_hash")?;
     }
+    policy.quorum_ttl = row.get::<_, i64>("quorum_ttl")?.try_into().ok();
+    policy.effective_micromark = row.get::<_, i64>("effective_micromarks")?.try_into().ok();
+    policy.expiration_micromark = row.get::<_, i64>("expires_micromarks")?.try_into().ok();
+    policy.max_record_bytes = row
+        .get::<_, Option<i64>>("max_record_bytes")?
+        .and_then(|n| n.try_into().ok());
+    policy.max_data_bytes = row
+        .get::<_, Option<i64>>("max_data_bytes")?
+        .and_then(|n| n.try_into().ok());
+    policy.inherit = row.get::<_, Option<i64>>("inherit")?.unwrap_or(1) != 0;
     Ok(policy)
 }
 
-pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
+/// The 📜 most recently set for `scope`, whether or not it's in force yet.
+pub fn retrieve_policy(conn: &Connection, scope: &str) -> Result<Option<Policy>, RhexError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS}
+         FROM policies
+         WHERE scope = ?1
+         ORDER BY COALESCE(set_at, 0) DESC, effective_micromarks DESC
+         LIMIT 1"
+    ))?;
+    let mut rows = stmt.query(params![scope])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(row_to_policy(row, scope)?)),
+        None => Ok(None),
+    }
+}
+
+/// The 📜 in force for `scope` at micromark `at`: the newest one set and
+/// effective by then. It replaces every older one, so if it has expired
+/// by `at` it's still returned and appends are denied until another is
+/// set.
+pub fn policy_at(conn: &Connection, scope: &str, at: u64) -> Result<Option<Policy>, RhexError> {
+    let at = at as i64;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS}
+         FROM policies
+         WHERE scope = ?1
+           AND COALESCE(set_at, 0) <= ?2
+           AND effective_micromarks <= ?2
+         ORDER BY effective_micromarks DESC, COALESCE(set_at, 0) DESC
+         LIMIT 1"
+    ))?;
+    let mut rows = stmt.query(params![scope, at])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(row_to_policy(row, scope)?)),
+        None => Ok(None),
+    }
+}
+
+/// The 📜 set by the record `current_hash`.
+pub fn retrieve_policy_by_hash(
+    conn: &Connection,
+    current_hash: &[u8; 32],
+) -> Result<Option<Policy>, RhexError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT scope, {COLUMNS} FROM policies WHERE current_hash = ?1"
+    ))?;
+    let row = stmt
+        .query_row(params![current_hash], |row| {
+            let scope: String = row.get("scope")?;
+            Ok(row_to_policy(row, &scope))
+        })
+        .optional()?;
+    row.transpose()
+}
+
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS policies (
             scope TEXT,
@@ -108,9 +172,85 @@ pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
             effective_micromarks INTEGER,
             expires_micromarks INTEGER,
             current_hash BLOB,
+            max_record_bytes INTEGER,
+            max_data_bytes INTEGER,
+            inherit INTEGER NOT NULL DEFAULT 1,
+            policy TEXT,
+            set_at INTEGER,
             PRIMARY KEY (current_hash)
         )",
         [],
     )?;
+    // Caches built before these columns existed.
+    for (column, decl) in [
+        ("max_record_bytes", "INTEGER"),
+        ("max_data_bytes", "INTEGER"),
+        ("inherit", "INTEGER NOT NULL DEFAULT 1"),
+        ("policy", "TEXT"),
+        ("set_at", "INTEGER"),
+    ] {
+        let exists = conn
+            .prepare("SELECT 1 FROM pragma_table_info('policies') WHERE name = ?1")?
+            .exists(params![column])?;
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE policies ADD COLUMN {column} {decl}"),
+                [],
+            )?;
+        }
+    }
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS policies_scope_effective
+         ON policies (scope, effective_micromarks)",
+        [],
+    )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hodeauxledger_core::policy::rule::Rule;
+
+    fn policy(rate: u64, effective: Option<u64>, expires: Option<u64>) -> Policy {
+        let mut p = Policy::new("core", vec![Rule::new("record:text", &["👑"], 1, rate)]);
+        p.effective_micromark = effective;
+        p.expiration_micromark = expires;
+        p
+    }
+
+    fn rate_at(conn: &Connection, at: u64) -> Option<u64> {
+        policy_at(conn, "core", at)
+            .unwrap()
+            .map(|p| p.rules[0].rate_per_mark)
+    }
+
+    #[test]
+    fn keeps_every_version() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        cache_policy(&conn, "core", &policy(1, None, None), &[1u8; 32], 100).unwrap();
+        // Set at 200, scheduled for 300, dead at 500.
+        cache_policy(
+            &conn,
+            "core",
+            &policy(2, Some(300), Some(500)),
+            &[2u8; 32],
+            200,
+        )
+        .unwrap();
+        // Backdated, but only counts from when it was set.
+        cache_policy(&conn, "core", &policy(3, Some(0), None), &[3u8; 32], 600).unwrap();
+
+        assert_eq!(rate_at(&conn, 50), None);
+        assert_eq!(rate_at(&conn, 250), Some(1));
+        assert_eq!(rate_at(&conn, 300), Some(2));
+        assert_eq!(rate_at(&conn, 550), Some(2));
+        assert_eq!(rate_at(&conn, 600), Some(3));
+
+        let latest = retrieve_policy(&conn, "core").unwrap().unwrap();
+        assert_eq!(latest.rules[0].rate_per_mark, 3);
+        let old = retrieve_policy_by_hash(&conn, &[2u8; 32]).unwrap().unwrap();
+        assert_eq!(old.expiration_micromark, Some(500));
+    }
+}
@@ -0,0 +1,88 @@
+use hodeauxledger_core::RhexError;
+use hodeauxledger_core::policy::rate::{self, RateDecision, RateState};
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+
+/// Rate limiter state per (scope, 📄, author key). Not part of
+/// `flush_everything`: it has to outlive restarts or a restart would reset
+/// everyone's allowance.
+pub fn retrieve_rate(
+    conn: &Connection,
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+) -> Result<Option<RateState>, RhexError> {
+    let state = conn
+        .query_row(
+            "SELECT mark, count FROM rate_limits
+             WHERE scope = ?1 AND record_type = ?2 AND author_public_key = ?3",
+            params![scope, record_type, author_public_key],
+            |row| {
+                Ok(RateState {
+                    mark: row.get("mark")?,
+                    count: row.get("count")?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(state)
+}
+
+pub fn store_rate(
+    conn: &Connection,
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+    state: &RateState,
+) -> Result<(), RhexError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO rate_limits (scope, record_type, author_public_key, mark, count)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            scope,
+            record_type,
+            author_public_key,
+            state.mark,
+            state.count
+        ],
+    )?;
+    Ok(())
+}
+
+/// Count one append against `rate_per_mark` and store the new state. The
+/// read and the update share one write transaction so concurrent
+/// connections can't both slip in under the limit.
+pub fn take_rate(
+    conn: &mut Connection,
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+    rate_per_mark: u64,
+    at: u64,
+) -> Result<RateDecision, RhexError> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let state = retrieve_rate(&tx, scope, record_type, author_public_key)?;
+    let (decision, state) = rate::take(state, rate_per_mark, at);
+    store_rate(&tx, scope, record_type, author_public_key, &state)?;
+    tx.commit()?;
+    Ok(decision)
+}
+
+pub fn flush_rates(conn: &Connection) -> Result<(), RhexError> {
+    conn.execute("DELETE FROM rate_limits", [])?;
+    Ok(())
+}
+
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS rate_limits (
+            scope TEXT,
+            record_type TEXT,
+            author_public_key BLOB,
+            mark INTEGER,
+            count INTEGER,
+            PRIMARY KEY (scope, record_type, author_public_key)
+        )",
+        [],
+    )?;
+    Ok(())
+}
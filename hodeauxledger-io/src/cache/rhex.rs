@@ -1,14 +1,17 @@
-use hodeauxledger_core::Rhex;
-use rusqlite::{Connection, params};
+use hodeauxledger_core::rhex::magic::{Magic, VERSION_0};
+use hodeauxledger_core::{Rhex, RhexError};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde_json::Value;
 use std::convert::TryInto;
 
-pub fn cache_rhex(conn: &Connection, rhex: &Rhex) -> anyhow::Result<()> {
+/// Cache `rhex`. A record already cached under its ⬇️🧬 is left as is.
+pub fn cache_rhex(conn: &Connection, rhex: &Rhex) -> Result<(), RhexError> {
     let sig_string = serde_json::to_string(&rhex.signatures)?;
     let data_string = serde_json::to_string(&rhex.intent.data)?;
-    let current_hash = rhex.current_hash.unwrap();
-    let mut stmt = conn.prepare("INSERT INTO rhex (previous_hash, scope, nonce, at, author_public_key, usher_public_key, record_type, data, signatures, current_hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)")?;
+    let current_hash = rhex.current_hash()?;
+    let mut stmt = conn.prepare("INSERT OR IGNORE INTO rhex (magic, previous_hash, scope, nonce, at, author_public_key, usher_public_key, record_type, data, signatures, current_hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)")?;
     stmt.execute(params![
+        rhex.magic,
         rhex.intent.previous_hash,
         rhex.intent.scope,
         rhex.intent.nonce,
@@ -23,18 +26,30 @@ pub fn cache_rhex(conn: &Connection, rhex: &Rhex) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn retrieve_scope_rhex(conn: &Connection, scope: &str) -> anyhow::Result<Vec<Rhex>> {
+/// Every record in `scope`, banned ones as tombstones.
+pub fn retrieve_scope_rhex(conn: &Connection, scope: &str) -> Result<Vec<Rhex>, RhexError> {
+    query_scope_rhex(conn, scope, false)
+}
+
+/// Every record in `scope` as it was appended, banned ones included.
+pub fn retrieve_scope_rhex_audit(conn: &Connection, scope: &str) -> Result<Vec<Rhex>, RhexError> {
+    query_scope_rhex(conn, scope, true)
+}
+
+fn query_scope_rhex(conn: &Connection, scope: &str, audit: bool) -> Result<Vec<Rhex>, RhexError> {
     let mut stmt = conn.prepare(
-        "SELECT previous_hash, scope, nonce, at,
+        "SELECT magic, previous_hash, rhex.scope AS scope, nonce, rhex.at AS at,
                 author_public_key, usher_public_key,
-                record_type, data, signatures, current_hash
+                record_type, data, signatures, current_hash, bans.ban_hash AS ban_hash
          FROM rhex
-         WHERE scope = ?1",
+         LEFT JOIN bans ON bans.target = rhex.current_hash
+         WHERE rhex.scope = ?1",
     )?;
     let mut rows = stmt.query(params![scope])?;
     let mut out_rhex = Vec::new();
     while let Some(row) = rows.next()? {
         // Simple string/primitive gets
+        let magic = magic_from_row(row)?;
         let ph: Vec<u8> = row.get("previous_hash")?;
         let scope: String = row.get("scope")?;
         let nonce: String = row.get("nonce")?;
@@ -45,30 +60,87 @@ pub fn retrieve_scope_rhex(conn: &Connection, scope: &str) -> anyhow::Result<Vec
         let data_str: String = row.get("data")?;
         let sig_str: String = row.get("signatures")?;
         let curr: Vec<u8> = row.get("current_hash")?;
-        out_rhex.push(Rhex {
-            magic: *b"RHEX\x00\x00",
+        let ban_hash: Option<[u8; 32]> = row.get("ban_hash")?;
+        let rhex = Rhex {
+            magic,
             intent: hodeauxledger_core::rhex::intent::Intent {
                 previous_hash: ph
                     .try_into()
-                    .map_err(|_| anyhow::anyhow!("previous_hash not 32 bytes"))?,
+                    .map_err(|_| RhexError::Cache("previous_hash not 32 bytes".into()))?,
                 scope,
                 nonce,
-                author_public_key: author_pk.as_slice().try_into().unwrap(),
-                usher_public_key: usher_pk.as_slice().try_into().unwrap(),
+                author_public_key: author_pk
+                    .try_into()
+                    .map_err(|_| RhexError::Cache("author_public_key not 32 bytes".into()))?,
+                usher_public_key: usher_pk
+                    .try_into()
+                    .map_err(|_| RhexError::Cache("usher_public_key not 32 bytes".into()))?,
                 record_type,
                 data: serde_json::from_str(&data_str)?,
             },
             context: hodeauxledger_core::rhex::context::Context { at: at as u64 },
             signatures: serde_json::from_str(&sig_str)?,
-            current_hash: Some(curr.to_vec().try_into().unwrap()),
-        });
+            current_hash: Some(
+                curr.try_into()
+                    .map_err(|_| RhexError::Cache("current_hash not 32 bytes".into()))?,
+            ),
+        };
+        match ban_hash {
+            Some(ban_hash) if !audit => {
+                out_rhex.push(rhex.tombstone(&ban_hash).map_err(RhexError::encoding)?)
+            }
+            _ => out_rhex.push(rhex),
+        }
     }
     Ok(out_rhex)
 }
 
-pub fn retrieve_rhex(conn: &Connection, current_hash: &[u8; 32]) -> anyhow::Result<Rhex> {
+/// ⬇️🧬 and ⏱️ of every record in `scope` signed by `author_public_key` at
+/// or after micromark `since`, oldest first.
+pub fn retrieve_signed_since(
+    conn: &Connection,
+    scope: &str,
+    author_public_key: &[u8; 32],
+    since: u64,
+) -> Result<Vec<([u8; 32], u64)>, RhexError> {
+    let mut stmt = conn.prepare(
+        "SELECT current_hash, at
+         FROM rhex
+         WHERE scope = ?1 AND author_public_key = ?2 AND at >= ?3
+         ORDER BY at",
+    )?;
+    let rows = stmt.query_map(params![scope, author_public_key, since as i64], |row| {
+        Ok((row.get::<_, [u8; 32]>(0)?, row.get::<_, i64>(1)? as u64))
+    })?;
+    let mut out = Vec::new();
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
+/// ⏱️ of the newest record cached for `scope`, which is its head's.
+pub fn retrieve_latest_at(conn: &Connection, scope: &str) -> Result<Option<u64>, RhexError> {
+    let mut stmt = conn.prepare("SELECT MAX(at) FROM rhex WHERE scope = ?1")?;
+    let at: Option<i64> = stmt.query_row(params![scope], |row| row.get(0))?;
+    Ok(at.map(|at| at as u64))
+}
+
+/// ⬇️🧬 and ⏱️ of the head of `scope`: its latest record, as ⏱️ only moves
+/// forward along a chain.
+pub fn retrieve_head(conn: &Connection, scope: &str) -> Result<Option<([u8; 32], u64)>, RhexError> {
+    let mut stmt = conn
+        .prepare("SELECT current_hash, at FROM rhex WHERE scope = ?1 ORDER BY at DESC LIMIT 1")?;
+    Ok(stmt
+        .query_row(params![scope], |row| {
+            Ok((row.get("current_hash")?, row.get::<_, i64>("at")? as u64))
+        })
+        .optional()?)
+}
+
+pub fn retrieve_rhex(conn: &Connection, current_hash: &[u8; 32]) -> Result<Rhex, RhexError> {
     let mut stmt = conn.prepare(
-        "SELECT previous_hash, scope, nonce, at,
+        "SELECT magic, previous_hash, scope, nonce, at,
                 author_public_key, usher_public_key,
                 record_type, data, signatures, current_hash
          FROM rhex
@@ -94,6 +166,7 @@ pub fn retrieve_rhex(conn: &Connection, current_hash: &[u8; 32]) -> anyhow::Resu
     };
 
     if let Some(row) = rows.next()? {
+        out_rhex.magic = magic_from_row(row)?;
         // Simple string/primitive gets
         out_rhex.intent.previous_hash = row.get::<_, [u8; 32]>("previous_hash")?;
         out_rhex.intent.scope = row.get::<_, String>("scope")?;
@@ -106,12 +179,12 @@ pub fn retrieve_rhex(conn: &Connection, current_hash: &[u8; 32]) -> anyhow::Resu
         let author_pk: Vec<u8> = row.get("author_public_key")?;
         out_rhex.intent.author_public_key = author_pk
             .try_into()
-            .map_err(|_| anyhow::anyhow!("author_public_key not 32 bytes"))?;
+            .map_err(|_| RhexError::Cache("author_public_key not 32 bytes".into()))?;
 
         let usher_pk: Vec<u8> = row.get("usher_public_key")?;
         out_rhex.intent.usher_public_key = usher_pk
             .try_into()
-            .map_err(|_| anyhow::anyhow!("usher_public_key not 32 bytes"))?;
+            .map_err(|_| RhexError::Cache("usher_public_key not 32 bytes".into()))?;
 
         // JSON blobs
         let data_str: String = row.get("data")?;
@@ -124,28 +197,29 @@ pub fn retrieve_rhex(conn: &Connection, current_hash: &[u8; 32]) -> anyhow::Resu
         let curr: Vec<u8> = row.get("current_hash")?;
         out_rhex.current_hash = Some(
             curr.try_into()
-                .map_err(|_| anyhow::anyhow!("current_hash not 32 bytes"))?,
+                .map_err(|_| RhexError::Cache("current_hash not 32 bytes".into()))?,
         );
     }
 
     Ok(out_rhex)
 }
 
-pub fn evict_rhex(conn: &Connection, current_hash: &[u8; 32]) -> anyhow::Result<()> {
+pub fn evict_rhex(conn: &Connection, current_hash: &[u8; 32]) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM rhex WHERE current_hash = ?1")?;
     stmt.execute(params![current_hash])?;
     Ok(())
 }
 
-pub fn flush_rhex(conn: &Connection) -> anyhow::Result<()> {
+pub fn flush_rhex(conn: &Connection) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM rhex")?;
     stmt.execute([])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS rhex (
+            magic BLOB,
             previous_hash BLOB,
             scope TEXT,
             nonce TEXT,
@@ -159,5 +233,65 @@ pub fn build_table(conn: &Connection) -> anyhow::Result<()> {
         )",
         [],
     )?;
+    // Caches built before the magic column existed.
+    let has_magic = conn
+        .prepare("SELECT 1 FROM pragma_table_info('rhex') WHERE name = 'magic'")?
+        .exists([])?;
+    if !has_magic {
+        conn.execute("ALTER TABLE rhex ADD COLUMN magic BLOB", [])?;
+    }
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS rhex_scope_at ON rhex (scope, at)",
+        [],
+    )?;
     Ok(())
 }
+
+/// Rows cached before the magic column existed are version 0 records.
+fn magic_from_row(row: &rusqlite::Row) -> Result<[u8; 6], RhexError> {
+    let magic: Option<Vec<u8>> = row.get("magic")?;
+    match magic {
+        Some(m) => m
+            .try_into()
+            .map_err(|_| RhexError::Cache("magic not 6 bytes".into())),
+        None => Ok(Magic::with_version(VERSION_0)?.to_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hodeauxledger_core::Intent;
+
+    fn record(previous_hash: [u8; 32], nonce: &str, at: u64) -> Rhex {
+        let mut rhex = Rhex::draft(Intent::new(
+            &previous_hash,
+            "core",
+            nonce,
+            &[1u8; 32],
+            &[2u8; 32],
+            "record:text",
+            serde_json::json!({ "text": nonce }),
+        ));
+        rhex.context.at = at;
+        rhex.finalize().unwrap()
+    }
+
+    #[test]
+    fn caching_twice_keeps_the_head() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        crate::cache::bans::build_table(&conn).unwrap();
+        let a = record([0u8; 32], "a", 10);
+        let b = record(a.current_hash.unwrap(), "b", 20);
+        for rhex in [&a, &b, &b, &a] {
+            cache_rhex(&conn, rhex).unwrap();
+        }
+        assert_eq!(retrieve_scope_rhex(&conn, "core").unwrap().len(), 2);
+        assert_eq!(
+            retrieve_head(&conn, "core").unwrap(),
+            Some((b.current_hash.unwrap(), 20))
+        );
+        assert_eq!(retrieve_head(&conn, "other").unwrap(), None);
+    }
+}
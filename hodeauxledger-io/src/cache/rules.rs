@@ -1,31 +1,42 @@
+use hodeauxledger_core::RhexError;
 use hodeauxledger_core::policy::rule::Rule;
 use rusqlite::{Connection, params};
 
-pub fn cache_rule(conn: &Connection, rule: &Rule, scope: &str) -> Result<(), anyhow::Error> {
+pub fn cache_rule(conn: &Connection, rule: &Rule, scope: &str) -> Result<(), RhexError> {
     let record_type = rule.record_type.clone();
     let rate = rule.rate_per_mark as i64;
     let roles = rule.append_roles.join(",");
     let quorum = rule.quorum_k as i64;
     let quorum_roles = rule.quorum_roles.join(",");
+    let deny = rule.deny as i64;
 
     conn.execute(
         "DELETE FROM rules WHERE scope = ?1 AND record_type = ?2",
-        params![scope],
+        params![scope, record_type],
     )?;
 
     conn.execute(
         "INSERT INTO rules (
-            scope, record_type, rate, roles, quorum, quorum_roles
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![scope, record_type, rate, roles, quorum, quorum_roles,],
+            scope, record_type, rate, roles, quorum, quorum_roles, deny
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![scope, record_type, rate, roles, quorum, quorum_roles, deny],
     )?;
 
     Ok(())
 }
 
-pub fn retrieve_rules(conn: &Connection, scope: &str) -> Result<Vec<Rule>, anyhow::Error> {
+/// Make `rules` the scope's whole rule set.
+pub fn replace_rules(conn: &Connection, scope: &str, rules: &[Rule]) -> Result<(), RhexError> {
+    conn.execute("DELETE FROM rules WHERE scope = ?1", params![scope])?;
+    for rule in rules {
+        cache_rule(conn, rule, scope)?;
+    }
+    Ok(())
+}
+
+pub fn retrieve_rules(conn: &Connection, scope: &str) -> Result<Vec<Rule>, RhexError> {
     let mut stmt = conn.prepare(
-        "SELECT record_type, rate, roles, quorum, quorum_roles
+        "SELECT record_type, rate, roles, quorum, quorum_roles, deny
          FROM rules
          WHERE scope = ?1",
     )?;
@@ -37,30 +48,32 @@ pub fn retrieve_rules(conn: &Connection, scope: &str) -> Result<Vec<Rule>, anyho
         let roles: String = row.get("roles")?;
         let quorum: i64 = row.get("quorum")?;
         let quorum_roles: String = row.get("quorum_roles")?;
+        let deny: Option<i64> = row.get("deny")?;
         rules.push(Rule {
             record_type,
             rate_per_mark: rate as u64,
             append_roles: roles.split(",").map(|s| s.to_string()).collect(),
             quorum_k: quorum as u8,
             quorum_roles: quorum_roles.split(",").map(|s| s.to_string()).collect(),
+            deny: deny.unwrap_or(0) != 0,
         });
     }
     Ok(rules)
 }
 
-pub fn evict_rule(conn: &Connection, scope: &str, record_type: &str) -> Result<(), anyhow::Error> {
+pub fn evict_rule(conn: &Connection, scope: &str, record_type: &str) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM rules WHERE scope = ?1 AND record_type = ?2")?;
     stmt.execute(params![scope, record_type])?;
     Ok(())
 }
 
-pub fn flush_rules(conn: &Connection) -> Result<(), anyhow::Error> {
+pub fn flush_rules(conn: &Connection) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM rules")?;
     stmt.execute([])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS rules (
             scope TEXT,
@@ -70,9 +83,20 @@ pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
             roles TEXT,
             quorum INTEGER,
             quorum_roles TEXT,
+            deny INTEGER NOT NULL DEFAULT 0,
             PRIMARY KEY (scope, record_type)
         )",
         [],
     )?;
+    // Caches built before rules could deny.
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info('rules') WHERE name = 'deny'")?
+        .exists([])?;
+    if !exists {
+        conn.execute(
+            "ALTER TABLE rules ADD COLUMN deny INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
     Ok(())
 }
@@ -1,4 +1,5 @@
-use rusqlite::{Connection, params};
+use hodeauxledger_core::RhexError;
+use rusqlite::{Connection, OptionalExtension, params};
 
 pub fn cache_scope(
     conn: &Connection,
@@ -6,17 +7,57 @@ pub fn cache_scope(
     role: &str,
     last_synced: &u64,
     head: &[u8; 32],
-) -> Result<(), anyhow::Error> {
-    let mut stmt = conn
-        .prepare("INSERT INTO scopes (scope, role, last_synced, head) VALUES (?1, ?2, ?3, ?4)")?;
+) -> Result<(), RhexError> {
+    // A seal can land before the scope itself is cached; keep it.
+    let mut stmt = conn.prepare(
+        "INSERT INTO scopes (scope, role, last_synced, head) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(scope) DO UPDATE SET
+            role = excluded.role,
+            last_synced = excluded.last_synced,
+            head = excluded.head",
+    )?;
     stmt.execute(params![scope, role, last_synced, head])?;
     Ok(())
 }
 
+/// Mark `scope` sealed by the scope:seal `seal_hash` from micromark `at`.
+pub fn seal_scope(
+    conn: &Connection,
+    scope: &str,
+    seal_hash: &[u8; 32],
+    at: u64,
+) -> Result<(), RhexError> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO scopes (scope, role, last_synced, head, sealed_by, sealed_at)
+         VALUES (?1, 'cache', 0, ?2, ?3, ?4)
+         ON CONFLICT(scope) DO UPDATE SET
+            sealed_by = excluded.sealed_by,
+            sealed_at = excluded.sealed_at",
+    )?;
+    stmt.execute(params![scope, [0u8; 32], seal_hash, at as i64])?;
+    Ok(())
+}
+
+/// ⬇️🧬 of the scope:seal on `scope` and the micromark it took effect.
+pub fn retrieve_seal(conn: &Connection, scope: &str) -> Result<Option<([u8; 32], u64)>, RhexError> {
+    let mut stmt = conn.prepare(
+        "SELECT sealed_by, sealed_at FROM scopes
+         WHERE scope = ?1 AND sealed_by IS NOT NULL",
+    )?;
+    Ok(stmt
+        .query_row(params![scope], |row| {
+            Ok((
+                row.get("sealed_by")?,
+                row.get::<_, i64>("sealed_at")? as u64,
+            ))
+        })
+        .optional()?)
+}
+
 pub fn retrieve_scope(
     conn: &Connection,
     scope: &str,
-) -> Result<(String, u64, [u8; 32]), anyhow::Error> {
+) -> Result<(String, u64, [u8; 32]), RhexError> {
     let mut stmt = conn.prepare(
         "SELECT role, last_synced, head
          FROM scopes
@@ -34,27 +75,55 @@ pub fn retrieve_scope(
     Ok((role, last_synced, head))
 }
 
-pub fn evict_scope(conn: &Connection, scope: &str) -> Result<(), anyhow::Error> {
+pub fn evict_scope(conn: &Connection, scope: &str) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM scopes WHERE scope = ?1")?;
     stmt.execute(params![scope])?;
     Ok(())
 }
 
-pub fn flush_scopes(conn: &Connection) -> Result<(), anyhow::Error> {
+pub fn flush_scopes(conn: &Connection) -> Result<(), RhexError> {
     let mut stmt = conn.prepare("DELETE FROM scopes")?;
     stmt.execute([])?;
     Ok(())
 }
 
-pub fn build_table(conn: &Connection) -> Result<(), anyhow::Error> {
+pub fn build_table(conn: &Connection) -> Result<(), RhexError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS scopes (
             scope TEXT PRIMARY KEY,
             role TEXT,
             last_synced INTEGER,
-            head BLOB
+            head BLOB,
+            sealed_by BLOB,
+            sealed_at INTEGER
         )",
         [],
     )?;
+    // Caches built before scopes could be sealed.
+    let has_sealed = conn
+        .prepare("SELECT 1 FROM pragma_table_info('scopes') WHERE name = 'sealed_by'")?
+        .exists([])?;
+    if !has_sealed {
+        conn.execute("ALTER TABLE scopes ADD COLUMN sealed_by BLOB", [])?;
+        conn.execute("ALTER TABLE scopes ADD COLUMN sealed_at INTEGER", [])?;
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_survives_caching_the_scope() {
+        let conn = Connection::open_in_memory().unwrap();
+        build_table(&conn).unwrap();
+        assert!(retrieve_seal(&conn, "a.b").unwrap().is_none());
+
+        seal_scope(&conn, "a.b", &[3u8; 32], 500).unwrap();
+        cache_scope(&conn, "a.b", "cache", &7, &[1u8; 32]).unwrap();
+
+        assert_eq!(retrieve_seal(&conn, "a.b").unwrap(), Some(([3u8; 32], 500)));
+        assert_eq!(retrieve_scope(&conn, "a.b").unwrap().2, [1u8; 32]);
+    }
+}
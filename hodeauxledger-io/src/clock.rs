@@ -0,0 +1,117 @@
+use std::sync::RwLock;
+
+use anyhow::{Result, anyhow, bail};
+use hodeauxledger_core::{GTClock, Rhex};
+use rusqlite::Connection;
+
+use crate::cache::{cache::Cache, epoch};
+use crate::disk::config::{NODE_CONFIG_PATH, load_node_config, save_node_config};
+
+/// The one GT clock every binary reads. Its epoch comes from the root
+/// scope:genesis and is kept in the cache and the node config, so
+/// whichever of the two a process can reach gives the same time.
+static EPOCH: RwLock<Option<i128>> = RwLock::new(None);
+
+/// Take the epoch from the root genesis `rhex`, store it in the cache
+/// and node config, and use it from here on. Seeing the same genesis
+/// again is fine; a different epoch is refused.
+pub fn adopt_genesis(conn: &Connection, config_path: &str, rhex: &Rhex) -> Result<GTClock> {
+    let clock = GTClock::from_genesis(rhex)?;
+    let genesis = rhex
+        .current_hash
+        .ok_or_else(|| anyhow!("root genesis has no ⬇️🧬"))?;
+    epoch::cache_epoch(conn, clock.epoch_unix_ms, &genesis)?;
+
+    let mut config = load_node_config(config_path)?;
+    match config.gt_epoch_unix_ms {
+        Some(ms) if ms as i128 != clock.epoch_unix_ms => bail!(
+            "{config_path} has GT epoch {ms} ms, root genesis says {} ms",
+            clock.epoch_unix_ms
+        ),
+        Some(_) => {}
+        None => {
+            config.gt_epoch_unix_ms = Some(clock.epoch_unix_ms as u64);
+            save_node_config(config_path, &config)?;
+        }
+    }
+
+    *EPOCH.write().unwrap() = Some(clock.epoch_unix_ms);
+    Ok(clock)
+}
+
+/// The clock as stored in the cache, else the node config.
+pub fn load_clock(conn: Option<&Connection>, config_path: &str) -> Result<GTClock> {
+    // A cache from before the epoch was kept just doesn't know it.
+    let cached = conn.and_then(|c| epoch::retrieve_epoch(c).ok().flatten());
+    if let Some((ms, _)) = cached {
+        return Ok(GTClock::new(ms));
+    }
+    match load_node_config(config_path)?.gt_epoch_unix_ms {
+        Some(ms) => Ok(GTClock::new(ms.into())),
+        None => {
+            bail!("GT epoch unknown: no root scope:genesis processed and none in {config_path}")
+        }
+    }
+}
+
+/// This process's clock, from the default cache and node config.
+pub fn gt_clock() -> Result<GTClock> {
+    if let Some(ms) = *EPOCH.read().unwrap() {
+        return Ok(GTClock::new(ms));
+    }
+    let cache = Cache::connect("").ok();
+    let clock = load_clock(cache.as_ref().map(|c| &c.conn), NODE_CONFIG_PATH)?;
+    *EPOCH.write().unwrap() = Some(clock.epoch_unix_ms);
+    Ok(clock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hodeauxledger_core::{Intent, record::body::Payload, record::scope::ScopeGenesis};
+
+    fn root_genesis(unix_ms: u64) -> Rhex {
+        let body = ScopeGenesis {
+            unix_epoch_ms: Some(unix_ms),
+            ..Default::default()
+        };
+        Rhex::draft(Intent::new(
+            &[0u8; 32],
+            "",
+            "n",
+            &[1u8; 32],
+            &[1u8; 32],
+            "scope:genesis",
+            body.to_data().unwrap(),
+        ))
+        .finalize()
+        .unwrap()
+    }
+
+    #[test]
+    fn everyone_agrees_on_the_time() {
+        let dir = std::env::temp_dir().join(format!("hl-clock-{}", std::process::id()));
+        let config = dir.join("node.json");
+        let config = config.to_str().unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        epoch::build_table(&conn).unwrap();
+
+        let genesis = root_genesis(1_752_941_587_614);
+        let adopted = adopt_genesis(&conn, config, &genesis).unwrap();
+        let clocks = [
+            adopted,
+            GTClock::from_genesis(&genesis).unwrap(),
+            load_clock(Some(&conn), config).unwrap(),
+            load_clock(None, config).unwrap(),
+            gt_clock().unwrap(),
+        ];
+        let now = 1_760_000_000_000;
+        for clock in &clocks {
+            assert_eq!(clock.micromarks_at(now), adopted.micromarks_at(now));
+        }
+
+        adopt_genesis(&conn, config, &genesis).unwrap();
+        assert!(adopt_genesis(&conn, config, &root_genesis(1)).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
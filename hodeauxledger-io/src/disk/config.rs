@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use hodeauxledger_core::time::skew::SkewWindow;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+pub const NODE_CONFIG_PATH: &str = "./data/node.json";
+
+/// Settings this node keeps next to its ledger and cache.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeConfig {
+    /// GT epoch from the root genesis, in Unix ms.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gt_epoch_unix_ms: Option<u64>,
+    /// How far ⏱️ on records we usher may be from our clock.
+    #[serde(default)]
+    pub skew: SkewWindow,
+    /// How far ⏱️ on other ushers' records may be before we say so.
+    #[serde(default = "SkewWindow::plausible")]
+    pub peer_skew: SkewWindow,
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        Self {
+            gt_epoch_unix_ms: None,
+            skew: SkewWindow::default(),
+            peer_skew: SkewWindow::plausible(),
+        }
+    }
+}
+
+/// A missing file is an empty config.
+pub fn load_node_config(path: &str) -> Result<NodeConfig> {
+    let p = Path::new(path);
+    if !p.exists() {
+        return Ok(NodeConfig::default());
+    }
+    let data = fs::read(p).with_context(|| format!("read node config {:?}", p))?;
+    serde_json::from_slice(&data).with_context(|| format!("parse node config {:?}", p))
+}
+
+pub fn save_node_config(path: &str, config: &NodeConfig) -> Result<()> {
+    let p = Path::new(path);
+    if let Some(dir) = p.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("create {:?}", dir))?;
+    }
+    let tmp = p.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(config)?)
+        .with_context(|| format!("write node config {:?}", tmp))?;
+    fs::rename(&tmp, p).with_context(|| format!("rename {:?} -> {:?}", tmp, p))?;
+    Ok(())
+}
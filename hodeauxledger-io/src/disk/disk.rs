@@ -15,10 +15,9 @@ pub fn save_intent(path: &str, intent: &Intent) -> Result<()> {
     let p = Path::new(path);
     let tmp = p.with_extension("tmp");
 
-    let v = serde_cbor::value::to_value(intent)?;
-
-    let rhex_bytes =
-        serde_cbor::to_vec(&v).with_context(|| format!("serialize Rhex to CBOR for {:?}", tmp))?;
+    let rhex_bytes = intent
+        .canonical_bytes()
+        .with_context(|| format!("serialize Intent to CBOR for {:?}", tmp))?;
 
     fs::write(&tmp, rhex_bytes).with_context(|| format!("write temp Rhex file {:?}", tmp))?;
     fs::rename(&tmp, p).with_context(|| format!("rename {:?} -> {:?}", tmp, p))?;
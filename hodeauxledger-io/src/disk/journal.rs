@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use std::{fs, path::PathBuf};
+
+/// Work that has to happen all-or-nothing is written here first, then
+/// carried out, then removed. Whatever is left after a crash is finished
+/// on the next start.
+fn journal_dir(ledger_path: &str) -> PathBuf {
+    PathBuf::from(ledger_path).join(".journal")
+}
+
+pub fn write_journal(ledger_path: &str, id: &str, entry: &serde_json::Value) -> Result<()> {
+    let dir = journal_dir(ledger_path);
+    fs::create_dir_all(&dir).with_context(|| format!("create {:?}", dir))?;
+    let path = dir.join(format!("{id}.json"));
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(entry)?)
+        .with_context(|| format!("write journal {:?}", tmp))?;
+    fs::rename(&tmp, &path).with_context(|| format!("rename {:?} -> {:?}", tmp, path))?;
+    Ok(())
+}
+
+/// Unfinished entries as (id, entry), oldest id first. Half-written temp
+/// files never made it into the journal and are ignored.
+pub fn list_journals(ledger_path: &str) -> Result<Vec<(String, serde_json::Value)>> {
+    let dir = journal_dir(ledger_path);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|e| e != "json") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let data = fs::read(&path).with_context(|| format!("read journal {:?}", path))?;
+        out.push((id.to_string(), serde_json::from_slice(&data)?));
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(out)
+}
+
+pub fn remove_journal(ledger_path: &str, id: &str) -> Result<()> {
+    let path = journal_dir(ledger_path).join(format!("{id}.json"));
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("remove journal {:?}", path))?;
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 pub mod authorities;
+pub mod config;
 pub mod disk;
+pub mod journal;
 pub mod key;
 pub mod rhex;
 pub mod scope;
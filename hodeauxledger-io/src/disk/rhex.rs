@@ -1,28 +1,29 @@
-use anyhow::{Context, Result};
-use hodeauxledger_core::rhex::rhex::Rhex;
-use std::{fs, path::PathBuf};
+use hodeauxledger_core::{RhexError, rhex::rhex::Rhex};
+use std::{fs, io, path::PathBuf};
 
-pub fn save_rhex(path: &PathBuf, rhex: &Rhex) -> Result<()> {
+pub fn save_rhex(path: &PathBuf, rhex: &Rhex) -> Result<(), RhexError> {
     let tmp = path.with_extension("tmp");
 
-    let v = serde_cbor::value::to_value(rhex)?;
+    let rhex_bytes = rhex.pack()?;
 
-    let rhex_bytes =
-        serde_cbor::to_vec(&v).with_context(|| format!("serialize Rhex to CBOR for {:?}", tmp))?;
-
-    fs::write(&tmp, rhex_bytes).with_context(|| format!("write temp Rhex file {:?}", tmp))?;
-    fs::rename(&tmp, path).with_context(|| format!("rename {:?} -> {:?}", tmp, path))?;
+    fs::write(&tmp, rhex_bytes).map_err(|e| io_at(&tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| io_at(path, e))?;
     Ok(())
 }
 
-pub fn load_rhex(path: &PathBuf) -> Result<Rhex> {
-    let data = fs::read(path)?;
+pub fn load_rhex(path: &PathBuf) -> Result<Rhex, RhexError> {
+    let data = fs::read(path).map_err(|e| io_at(path, e))?;
     let rhex: Rhex = serde_cbor::from_slice(&data)
-        .with_context(|| format!("deserialize Rhex from CBOR for {:?}", path))?;
+        .map_err(|e| RhexError::Decoding(format!("{:?}: {}", path, e)))?;
     Ok(rhex)
 }
 
-pub fn load_raw_rhex(path: &PathBuf) -> Result<Vec<u8>> {
-    let data = fs::read(path)?;
+pub fn load_raw_rhex(path: &PathBuf) -> Result<Vec<u8>, RhexError> {
+    let data = fs::read(path).map_err(|e| io_at(path, e))?;
     Ok(data)
 }
+
+/// Keep the path around in io errors so they still say which file broke.
+fn io_at(path: &PathBuf, e: io::Error) -> RhexError {
+    RhexError::Io(io::Error::new(e.kind(), format!("{:?}: {}", path, e)))
+}
@@ -1,7 +1,7 @@
 use crate::cache::{cache::Cache, rhex::cache_rhex};
 use crate::disk::rhex::load_rhex;
 use anyhow::{Result, bail};
-use hodeauxledger_core::{rhex::rhex::Rhex, scope::table::ScopeTable};
+use hodeauxledger_core::{ScopeName, rhex::rhex::Rhex, scope::table::ScopeTable};
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -14,11 +14,17 @@ pub enum ScopeSink {
 }
 
 pub fn load_scope(ledger_path: &str, scope: &str, sink: ScopeSink) -> Result<Vec<Rhex>> {
+    // The name becomes a path; don't let it wander off.
+    ScopeName::parse(scope)?;
     let dir = format!("{}/{}", ledger_path, scope);
 
     let base = Path::new(&dir);
     let mut out = Vec::new();
-    let cache = Cache::connect("")?;
+    // Audits only want the records, don't drag a cache in for them.
+    let cache = match sink {
+        ScopeSink::Vec => None,
+        _ => Some(Cache::connect("")?),
+    };
 
     // 1) Load scope:genesis first
     let genesis_path =
@@ -38,10 +44,10 @@ pub fn load_scope(ledger_path: &str, scope: &str, sink: ScopeSink) -> Result<Vec
             out.push(curr);
         }
         ScopeSink::Db => {
-            cache_rhex(&cache.conn, &curr)?;
+            cache_rhex(&cache.as_ref().unwrap().conn, &curr)?;
         }
         ScopeSink::Both => {
-            cache_rhex(&cache.conn, &curr)?;
+            cache_rhex(&cache.as_ref().unwrap().conn, &curr)?;
             out.push(curr);
         }
     }
@@ -62,10 +68,10 @@ pub fn load_scope(ledger_path: &str, scope: &str, sink: ScopeSink) -> Result<Vec
                 out.push(candidate);
             }
             ScopeSink::Db => {
-                cache_rhex(&cache.conn, &candidate)?;
+                cache_rhex(&cache.as_ref().unwrap().conn, &candidate)?;
             }
             ScopeSink::Both => {
-                cache_rhex(&cache.conn, &candidate)?;
+                cache_rhex(&cache.as_ref().unwrap().conn, &candidate)?;
                 out.push(candidate);
             }
         }
@@ -94,11 +100,29 @@ pub fn save_scope_table(ledger_path: &str, table: &ScopeTable) -> Result<()> {
     }
     let mut filename = PathBuf::from(ledger_path);
     filename.push("scope_table.json");
-    let table_str = table.to_string();
-    fs::write(&filename, table_str)?;
+    let tmp = filename.with_extension("tmp");
+    fs::write(&tmp, table.to_string())?;
+    fs::rename(&tmp, &filename)?;
     Ok(())
 }
 
+/// Where the record following `previous_hash` lives in `scope`.
+pub fn record_path(ledger_path: &str, scope: &str, previous_hash: &[u8; 32]) -> Result<PathBuf> {
+    ScopeName::parse(scope)?;
+    Ok(PathBuf::from(ledger_path)
+        .join(scope)
+        .join(format!("{}.rhex", to_hex(previous_hash))))
+}
+
+/// ⬇️🧬 of the last record in `scope` on disk.
+pub fn scope_head(ledger_path: &str, scope: &str) -> Result<[u8; 32]> {
+    let records = load_scope(ledger_path, scope, ScopeSink::Vec)?;
+    match records.last().and_then(|r| r.current_hash) {
+        Some(head) => Ok(head),
+        None => bail!("🌐:{scope} has no head"),
+    }
+}
+
 pub fn to_hex(bytes: &[u8]) -> String {
     let mut s = String::with_capacity(bytes.len() * 2);
     for &b in bytes {
@@ -1,4 +1,5 @@
 pub mod cache;
+pub mod clock;
 pub mod disk;
 pub mod net;
 pub mod pipe;
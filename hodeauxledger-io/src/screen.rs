@@ -1,10 +1,10 @@
-use hodeauxledger_core::Rhex;
 use hodeauxledger_core::crypto::b64::to_base64;
+use hodeauxledger_core::{GtTime, Rhex};
 use owo_colors::OwoColorize;
 
 pub fn pretty_print_rhex(rhex: &Rhex) -> Result<(), anyhow::Error> {
     println!("{{");
-    println!("  {}: {:?}", "🪄", rhex.magic.yellow());
+    println!("  {}: {}", "🪄", magic_summary(rhex).yellow());
     println!("  {}: {{", "🎯");
     println!(
         "    {}: {}",
@@ -31,7 +31,12 @@ pub fn pretty_print_rhex(rhex: &Rhex) -> Result<(), anyhow::Error> {
     );
     println!("  }}");
     println!("  {}: {{", "🖼️");
-    println!("    {}: {}", "⏱️", rhex.context.at.yellow());
+    println!(
+        "    {}: {} ({})",
+        "⏱️",
+        GtTime::from(rhex.context.at).yellow(),
+        rhex.context.at
+    );
     println!("  }}");
     println!("  🖊️🖊️🖊️: [");
     for s in &rhex.signatures {
@@ -63,3 +68,24 @@ fn sig_type_to_icon(sig_type: u8) -> String {
         _ => "?".to_string(),
     }
 }
+
+fn magic_summary(rhex: &Rhex) -> String {
+    let magic = match rhex.magic() {
+        Ok(m) => m,
+        Err(e) => return format!("{:?} ({})", rhex.magic, e),
+    };
+    let mut flags = Vec::new();
+    if magic.is_more() {
+        flags.push("more");
+    }
+    if magic.is_resume() {
+        flags.push("resume");
+    }
+    if magic.is_metadata() {
+        flags.push("metadata");
+    }
+    if magic.is_encrypted() {
+        flags.push("encrypted");
+    }
+    format!("✨{} [{}]", magic.version(), flags.join(", "))
+}
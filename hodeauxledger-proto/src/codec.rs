@@ -1,10 +1,10 @@
-use anyhow::{Context, Result};
 use bytes::{BufMut, BytesMut};
-use hodeauxledger_core::Rhex;
+use hodeauxledger_core::rhex::limits::{HARD_MAX_RECORD_BYTES, RecordLimits};
+use hodeauxledger_core::{Rhex, RhexError};
 use tokio_util::codec::{Decoder, Encoder};
 
 /// Fixed frame size (4 KiB) for on-the-wire R⬢ messages.
-pub const RHEX_FRAME_SIZE: usize = 4096;
+pub const RHEX_FRAME_SIZE: usize = HARD_MAX_RECORD_BYTES;
 
 /// Codec for encoding/decoding Rhex messages with 4 KiB fixed-size padding.
 #[derive(Debug)]
@@ -18,9 +18,9 @@ impl RhexCodec {
 
 impl Decoder for RhexCodec {
     type Item = Rhex;
-    type Error = anyhow::Error;
+    type Error = RhexError;
 
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, RhexError> {
         // Wait until we have a full frame.
         if src.len() < RHEX_FRAME_SIZE {
             return Ok(None);
@@ -35,34 +35,38 @@ impl Decoder for RhexCodec {
             Some(last_nonzero) => last_nonzero + 1,
             None => {
                 // Entire frame was zeros — treat as invalid/empty frame.
-                return Err(anyhow::anyhow!("empty padded frame (no CBOR payload)"));
+                return Err(RhexError::decoding("empty padded frame (no CBOR payload)"));
             }
         };
 
         let payload = &frame[..payload_len];
 
         // Decode CBOR into Rhex.
-        let rhex = Rhex::from_cbor(payload).context("Failed to decode Rhex from CBOR payload")?;
+        let rhex = Rhex::unpack(payload)?;
+
+        // Refuse formats we can't read and records over the size caps
+        // before anyone else touches them.
+        RecordLimits::for_rhex(&rhex)?.check_record_len(payload_len)?;
 
         Ok(Some(rhex))
     }
 }
 
 impl Encoder<Rhex> for RhexCodec {
-    type Error = anyhow::Error;
+    type Error = RhexError;
 
-    fn encode(&mut self, item: Rhex, dst: &mut BytesMut) -> Result<()> {
+    fn encode(&mut self, item: Rhex, dst: &mut BytesMut) -> Result<(), RhexError> {
         // Serialize to the canonical/stable CBOR form.
-
-        let cbor = Rhex::to_stable_cbor(&item)?;
+        let cbor = item.pack()?;
+        RecordLimits::for_rhex(&item)?.check_record_len(cbor.len())?;
 
         // Enforce frame size.
         if cbor.len() > RHEX_FRAME_SIZE {
-            return Err(anyhow::anyhow!(
-                "CBOR payload ({}) exceeds frame size ({})",
-                cbor.len(),
-                RHEX_FRAME_SIZE
-            ));
+            return Err(RhexError::SizeLimitExceeded {
+                what: "CBOR payload",
+                size: cbor.len(),
+                limit: RHEX_FRAME_SIZE,
+            });
         }
 
         // Ensure room for the whole padded frame.
@@ -1,17 +1,131 @@
-use hodeauxledger_core::{Key, Rhex};
+use hodeauxledger_core::{
+    Key, Rhex, RhexError,
+    policy::decision::{AppendDecision, DenyReason},
+    time::skew::TimeIssue,
+    to_base64,
+};
+use serde_json::Value;
 
-use crate::rhex::builder;
+use crate::rhex::{builder, validator::ContextIssue};
 
-pub fn verifiy_failed(
+/// Error reply of `record_type` about `rhex`. Only identifying bits of the
+/// failed record are echoed back so the reply stays inside the limits;
+/// `extra` fields go next to `code` and `error`.
+fn failed(
     our_key: &Key,
-    err: anyhow::Error,
+    record_type: &str,
+    code: &str,
+    error: String,
     rhex: &Rhex,
+    extra: &[(&str, Value)],
 ) -> Result<Rhex, anyhow::Error> {
-    let record_type = "error:verify_failed";
-    let data = serde_json::json!({
-        "failed_rhex": rhex,
-        "error": err.to_string(),
+    let mut data = serde_json::json!({
+        "failed": {
+            "scope": rhex.intent.scope,
+            "nonce": rhex.intent.nonce,
+            "record_type": rhex.intent.record_type,
+            "current_hash": rhex.current_hash.map(|h| to_base64(&h)),
+        },
+        "code": code,
+        "error": error,
     });
-    let rhex = builder::build_rhex(&[0u8; 32], "", our_key, &[0u8; 32], record_type, data);
-    Ok(rhex)
+    for (field, value) in extra {
+        data[*field] = value.clone();
+    }
+    builder::build_rhex(&[0u8; 32], "", our_key, &[0u8; 32], record_type, data)
+}
+
+/// Error reply for a R⬢ that failed verification.
+pub fn verifiy_failed(our_key: &Key, err: &RhexError, rhex: &Rhex) -> Result<Rhex, anyhow::Error> {
+    failed(
+        our_key,
+        "error:verify_failed",
+        err.code(),
+        err.to_string(),
+        rhex,
+        &[],
+    )
+}
+
+/// Error reply for a R⬢ that is valid on its own but not given the
+/// scope's keys and authorities.
+pub fn context_failed(
+    our_key: &Key,
+    issues: &[ContextIssue],
+    rhex: &Rhex,
+) -> Result<Rhex, anyhow::Error> {
+    failed(
+        our_key,
+        "error:verify_failed",
+        issues.first().map_or("context_invalid", |i| i.code()),
+        issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("; "),
+        rhex,
+        &[],
+    )
+}
+
+/// Error reply for a R⬢ the scope policy won't let its author append.
+pub fn append_denied(
+    our_key: &Key,
+    decision: &AppendDecision,
+    rhex: &Rhex,
+) -> Result<Rhex, anyhow::Error> {
+    let seal = match decision {
+        AppendDecision::Deny(DenyReason::ScopeSealed { seal, .. }) => {
+            vec![("seal", to_base64(seal).into())]
+        }
+        _ => Vec::new(),
+    };
+    failed(
+        our_key,
+        "error:append_denied",
+        "append_denied",
+        decision.to_string(),
+        rhex,
+        &seal,
+    )
+}
+
+/// Error reply for a R⬢ over its ↔️, saying when the author may retry.
+pub fn rate_limited(our_key: &Key, retry_at: u64, rhex: &Rhex) -> Result<Rhex, anyhow::Error> {
+    failed(
+        our_key,
+        "error:rate_limited",
+        "rate_limited",
+        format!("↔️ exceeded, retry at micromark {retry_at}"),
+        rhex,
+        &[("retry_at", retry_at.into())],
+    )
+}
+
+/// Error reply for a request we accepted but couldn't carry out.
+pub fn request_failed(
+    our_key: &Key,
+    err: &anyhow::Error,
+    rhex: &Rhex,
+) -> Result<Rhex, anyhow::Error> {
+    failed(
+        our_key,
+        "error:request_failed",
+        "request_failed",
+        format!("{err:#}"),
+        rhex,
+        &[],
+    )
+}
+
+/// Error reply for a R⬢ whose ⏱️ is out of order or too far from our clock.
+pub fn time_rejected(our_key: &Key, issue: &TimeIssue, rhex: &Rhex) -> Result<Rhex, anyhow::Error> {
+    failed(
+        our_key,
+        "error:time_rejected",
+        issue.code(),
+        issue.to_string(),
+        rhex,
+        &[("at", rhex.context.at.into())],
+    )
 }
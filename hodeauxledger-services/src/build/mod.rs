@@ -5,4 +5,5 @@ pub mod policy;
 pub mod record;
 pub mod request;
 pub mod schema;
+pub mod steward;
 pub mod system;
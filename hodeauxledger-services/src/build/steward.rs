@@ -0,0 +1,25 @@
+use hodeauxledger_core::{GtTime, Key, Rhex, time::skew::TimeIssue, to_base64};
+
+use crate::rhex::builder;
+
+/// 💩:⚠️ about a record another usher signed whose ⏱️ is implausibly far
+/// from our clock. The record itself still goes through; this is us
+/// saying we doubt its time.
+pub fn clock_warning(
+    our_key: &Key,
+    issue: &TimeIssue,
+    now: GtTime,
+    rhex: &Rhex,
+) -> Result<Rhex, anyhow::Error> {
+    let record_type = "steward:warning";
+    let data = serde_json::json!({
+        "🎯": rhex.current_hash.map(|h| to_base64(&h)),
+        "scope": rhex.intent.scope,
+        "usher": to_base64(&rhex.intent.usher_public_key),
+        "code": issue.code(),
+        "warning": issue.to_string(),
+        "at": rhex.context.at,
+        "seen_at": now.micromarks(),
+    });
+    builder::build_rhex(&[0u8; 32], "", our_key, &[0u8; 32], record_type, data)
+}
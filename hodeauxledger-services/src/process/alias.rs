@@ -0,0 +1,67 @@
+use anyhow::{Result, bail};
+use hodeauxledger_core::{
+    Alias, Rhex,
+    record::{
+        alias::{AliasGrant, AliasRevoke},
+        body::RecordBody,
+    },
+    to_base64,
+};
+use hodeauxledger_io::{Cache, cache};
+
+pub fn process_alias_records(
+    rhex: &Rhex,
+    body: &RecordBody,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    match body {
+        RecordBody::AliasGrant(b) => grant(rhex, b, first_time),
+        RecordBody::AliasRevoke(b) => revoke(rhex, b, first_time),
+        _ => {
+            anyhow::bail!("invalid record type: {}", rhex.intent.record_type.as_str());
+        }
+    }
+}
+
+/// alias:grant — names a record already in the scope. Granting a name that
+/// is in use repoints it.
+pub fn grant(rhex: &Rhex, body: &AliasGrant, first_time: bool) -> Result<Vec<Rhex>> {
+    let scope = &rhex.intent.scope;
+    Alias::check_name(&body.name)?;
+
+    let cache = Cache::connect("")?;
+    let target = cache::rhex::retrieve_rhex(&cache.conn, &body.target)?;
+    if target.current_hash.is_none() || target.intent.scope != *scope {
+        bail!(
+            "🅰️ {} points at {}, which isn't in 🌐:{scope}",
+            body.name,
+            to_base64(&body.target)
+        );
+    }
+
+    let alias = body.to_alias(scope);
+    cache::aliases::cache_alias(&cache.conn, &alias)?;
+    if first_time {
+        println!(
+            "🅰️:🟢 occurred in 🌐:{scope}: {} → {}",
+            alias.to_string(),
+            alias.to_resolved()
+        );
+    }
+    Ok(Vec::new())
+}
+
+/// alias:revoke — frees the name in this scope only.
+pub fn revoke(rhex: &Rhex, body: &AliasRevoke, first_time: bool) -> Result<Vec<Rhex>> {
+    let scope = &rhex.intent.scope;
+
+    let cache = Cache::connect("")?;
+    if cache::aliases::retrieve_alias(&cache.conn, scope, &body.name)?.is_none() {
+        bail!("no 🅰️ {} in 🌐:{scope}", body.name);
+    }
+    cache::aliases::evict_alias(&cache.conn, &body.name, scope)?;
+    if first_time {
+        println!("🅰️:🔴 occurred in 🌐:{scope} for {}", body.name);
+    }
+    Ok(Vec::new())
+}
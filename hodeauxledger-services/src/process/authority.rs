@@ -0,0 +1,68 @@
+use anyhow::Result;
+use hodeauxledger_core::{
+    Rhex,
+    record::{
+        authority::{AuthorityGrant, AuthorityRevoke},
+        body::RecordBody,
+    },
+    to_base64,
+};
+use hodeauxledger_io::{Cache, cache};
+
+use crate::scope::scope::update_scope_in_table;
+
+pub fn process_authority_records(
+    rhex: &Rhex,
+    body: &RecordBody,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    match body {
+        RecordBody::AuthorityGrant(b) => grant(rhex, b, first_time),
+        RecordBody::AuthorityRevoke(b) => revoke(rhex, b, first_time),
+        _ => {
+            anyhow::bail!("invalid record type: {}", rhex.intent.record_type.as_str());
+        }
+    }
+}
+
+/// authority:grant — the authority goes into the cache and, if we carry the
+/// scope, into its entry in the scope table. Granting a 🔓 again replaces it.
+pub fn grant(rhex: &Rhex, body: &AuthorityGrant, first_time: bool) -> Result<Vec<Rhex>> {
+    body.validate()?;
+    let scope = &rhex.intent.scope;
+    let authority = body.to_authority();
+
+    let cache = Cache::connect("")?;
+    cache::authorities::cache_authority(&cache.conn, scope, std::slice::from_ref(&authority))?;
+    update_scope_in_table(scope, |s| {
+        s.remove_authority_by_key(authority.public_key);
+        s.authorities.push(authority.clone());
+    })?;
+
+    if first_time {
+        println!(
+            "👑:🟢 occurred in 🌐:{scope} for {} ({})",
+            authority.to_string(),
+            to_base64(&authority.public_key)
+        );
+    }
+    Ok(Vec::new())
+}
+
+/// authority:revoke — drops the authority with that 🔓 from the cache and
+/// the scope table.
+pub fn revoke(rhex: &Rhex, body: &AuthorityRevoke, first_time: bool) -> Result<Vec<Rhex>> {
+    let scope = &rhex.intent.scope;
+
+    let cache = Cache::connect("")?;
+    cache::authorities::evict_authority(&cache.conn, scope, &body.public_key)?;
+    update_scope_in_table(scope, |s| s.remove_authority_by_key(body.public_key))?;
+
+    if first_time {
+        println!(
+            "👑:🔴 occurred in 🌐:{scope} for {}",
+            to_base64(&body.public_key)
+        );
+    }
+    Ok(Vec::new())
+}
@@ -0,0 +1,39 @@
+use anyhow::{Result, anyhow, bail};
+use hodeauxledger_core::{Rhex, record::ban::RecordBan, to_base64};
+use hodeauxledger_io::{Cache, cache};
+
+/// record:ban — withholds an earlier 📦 record of the same scope. Who may
+/// ban is down to the 📜 rule for record:ban, checked before we get here.
+/// The record stays in the cache, and queries get a tombstone in its place.
+pub fn ban(rhex: &Rhex, body: &RecordBan, first_time: bool) -> Result<Vec<Rhex>> {
+    let scope = &rhex.intent.scope;
+    let ban_hash = rhex
+        .current_hash
+        .ok_or_else(|| anyhow!("📦:🚫 has no ⬇️🧬"))?;
+
+    let cache = Cache::connect("")?;
+    let target = cache::rhex::retrieve_rhex(&cache.conn, &body.target)?;
+    if target.current_hash.is_none() || target.intent.scope != *scope {
+        bail!(
+            "📦:🚫 target {} isn't in 🌐:{scope}",
+            to_base64(&body.target)
+        );
+    }
+    RecordBan::check_target_type(&target.intent.record_type)?;
+    if let Some(earlier) = cache::bans::retrieve_ban(&cache.conn, &body.target)? {
+        bail!(
+            "{} is already banned by {}",
+            to_base64(&body.target),
+            to_base64(&earlier)
+        );
+    }
+
+    cache::bans::cache_ban(&cache.conn, scope, &body.target, &ban_hash, rhex.context.at)?;
+    if first_time {
+        println!(
+            "📦:🚫 occurred in 🌐:{scope} for {}",
+            to_base64(&body.target)
+        );
+    }
+    Ok(Vec::new())
+}
@@ -1,87 +1,122 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use ed25519_dalek::VerifyingKey;
-use hodeauxledger_core::{Key, Rhex, from_base64};
-use hodeauxledger_io::cache::cache::Cache;
-use std::convert::TryInto;
+use hodeauxledger_core::{
+    Key, Rhex,
+    key::revocation::RevokeReason,
+    record::{
+        body::RecordBody,
+        key::{KeyGrant, KeyRevoke},
+    },
+    to_base64,
+};
+use hodeauxledger_io::{Cache, cache};
 
-pub fn process_key_records(rhex: &Rhex, first_time: bool) -> Result<(), anyhow::Error> {
-    match rhex.intent.record_type.as_str() {
-        "🔑:🟢" | "key:grant" => grant(rhex, first_time),
-        "🔑:🔴" | "key:revoke" => revoke(rhex, first_time),
+pub fn process_key_records(
+    rhex: &Rhex,
+    body: &RecordBody,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    match body {
+        RecordBody::KeyGrant(b) => grant(rhex, b, first_time),
+        RecordBody::KeyRevoke(b) => revoke(rhex, b, first_time),
         _ => {
             anyhow::bail!("invalid record type: {}", rhex.intent.record_type.as_str());
         }
     }
 }
 
-pub fn grant(rhex: &Rhex, _first_time: bool) -> Result<()> {
+pub fn grant(rhex: &Rhex, body: &KeyGrant, first_time: bool) -> Result<Vec<Rhex>> {
     let cache = Cache::connect("")?;
     let scope = &rhex.intent.scope;
 
-    // roles: Option<Vec<String>>
-    let roles: Option<Vec<String>> = rhex
-        .intent
-        .data
-        .get("roles")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|val| val.as_str().map(|s| s.to_string()))
-                .collect::<Vec<String>>()
-        });
+    let verifying_key = VerifyingKey::from_bytes(&body.public_key)
+        .map_err(|e| anyhow!("invalid public_key: {e}"))?;
 
-    // effective_micromark: Option<u64>
-    let effective_micromark: Option<u64> = rhex
-        .intent
-        .data
-        .get("effective_micromark")
-        .and_then(|v| v.as_u64());
-
-    // expires_micromark: Option<u64>
-    let expires_micromark: Option<u64> = rhex
-        .intent
-        .data
-        .get("expires_micromark")
-        .and_then(|v| v.as_u64());
-
-    // public_key (base64/bytes) → VerifyingKey
-    let pk_b64 = rhex
-        .intent
-        .data
-        .get("public_key")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow!("missing public_key"))?;
-
-    let pk_bytes = from_base64(pk_b64)?; // Vec<u8>
-
-    // must be 32 bytes
-    let pk_arr: [u8; 32] = pk_bytes
-        .as_slice()
-        .try_into()
-        .map_err(|_| anyhow!("public_key must be 32 bytes"))?;
-
-    let verifying_key =
-        VerifyingKey::from_bytes(&pk_arr).map_err(|e| anyhow!("invalid public_key: {e}"))?;
+    // A compromised key stays dead; anything else can be granted again.
+    if let Some(old) = cache::key::retrieve_key(&cache.conn, scope, &body.public_key)?
+        && old
+            .revoked
+            .is_some_and(|r| r.reason == RevokeReason::Compromised)
+    {
+        bail!(
+            "🔑 {} was revoked as compromised in 🌐:{scope}",
+            to_base64(&body.public_key)
+        );
+    }
 
-    // Build the Key
     let key = Key {
-        roles,
+        roles: Some(body.roles.clone()),
         sk: None,
         pk: Some(verifying_key),
-        effective_micromark,
-        expires_micromark,
-        // add other fields if your Key struct has them (e.g., scope)
+        effective_micromark: Some(body.effective_micromark),
+        expires_micromark: Some(body.expires_micromark),
+        revoked: None,
     };
 
     // Persist
-    cache.cache_key(&key, scope)?;
+    cache::key::cache_grant(&cache.conn, scope, &key, rhex.current_hash.as_ref())?;
+    if first_time {
+        println!(
+            "🔑:🟢 occurred in 🌐:{scope} for {}",
+            to_base64(&body.public_key)
+        );
+    }
+
+    Ok(Vec::new())
+}
+
+/// Processes key:revoke. The key is kept in the cache with the revocation
+/// so records it signed can still be judged. For a compromise, lists the
+/// records it signed since the compromise started.
+pub fn revoke(rhex: &Rhex, body: &KeyRevoke, first_time: bool) -> Result<Vec<Rhex>> {
+    let cache = Cache::connect("")?;
+    let scope = &rhex.intent.scope;
+
+    if let Some(grant_ref) = body.grant_ref
+        && let Some(current) = cache::key::retrieve_grant_ref(&cache.conn, scope, &body.public_key)?
+        && current != grant_ref
+    {
+        bail!(
+            "🔑:🔴 names grant {}, but the key's grant is {}",
+            to_base64(&grant_ref),
+            to_base64(&current)
+        );
+    }
+
+    let revocation = body.revocation(rhex.current_hash);
+    cache::key::revoke_key(&cache.conn, scope, &body.public_key, &revocation)?;
+    if first_time {
+        println!(
+            "🔑:🔴 occurred in 🌐:{scope} for {} ({})",
+            to_base64(&body.public_key),
+            body.reason
+        );
+    }
 
-    Ok(())
+    if first_time && let Some(since) = revocation.suspect_from() {
+        let suspect =
+            cache::rhex::retrieve_signed_since(&cache.conn, scope, &body.public_key, since)?;
+        for (hash, at) in &suspect {
+            println!("⚠️ suspect R⬢ {} at {at}", to_base64(hash));
+        }
+    }
+    Ok(Vec::new())
 }
-pub fn revoke(rhex: &Rhex, first_time: bool) -> Result<(), anyhow::Error> {
+
+/// Records in `scope` signed by `public_key` since its compromise, if it
+/// was revoked as compromised.
+pub fn suspect_records(scope: &str, public_key: &[u8; 32]) -> Result<Vec<([u8; 32], u64)>> {
     let cache = Cache::connect("")?;
-    let _ = rhex;
-    let _ = first_time;
-    let _ = cache;
-    Ok(())
+    let Some(since) = cache::key::retrieve_key(&cache.conn, scope, public_key)?
+        .and_then(|k| k.revoked)
+        .and_then(|r| r.suspect_from())
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(cache::rhex::retrieve_signed_since(
+        &cache.conn,
+        scope,
+        public_key,
+        since,
+    )?)
 }
@@ -1,3 +1,7 @@
+pub mod alias;
+pub mod authority;
+pub mod ban;
 pub mod key;
+pub mod policy;
 pub mod request;
 pub mod scope;
@@ -0,0 +1,24 @@
+use anyhow::Result;
+use hodeauxledger_core::{Rhex, record::policy::PolicySet};
+use hodeauxledger_io::{Cache, cache};
+
+/// Processes policy:set. Every version is kept against its ⬇️🧬 so old
+/// records can still be judged by the 📜 of their time; the rules table
+/// follows the latest one.
+pub fn set(rhex: &Rhex, body: &PolicySet, first_time: bool) -> Result<Vec<Rhex>> {
+    let scope = &rhex.intent.scope;
+    let current_hash = rhex
+        .current_hash
+        .ok_or_else(|| anyhow::anyhow!("📜:🟢 has no ⬇️🧬"))?;
+    if first_time {
+        println!("📜:🟢 occurred for 🌐:{scope}");
+    }
+
+    let mut policy = body.policy.clone();
+    policy.scope = scope.clone();
+
+    let cache = Cache::connect("")?;
+    cache::policies::cache_policy(&cache.conn, scope, &policy, &current_hash, rhex.context.at)?;
+    cache::rules::replace_rules(&cache.conn, scope, &policy.rules)?;
+    Ok(Vec::new())
+}
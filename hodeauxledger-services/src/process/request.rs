@@ -1,4 +1,4 @@
-use hodeauxledger_core::{Intent, Rhex};
+use hodeauxledger_core::{Intent, Rhex, record::body::RecordBody};
 use hodeauxledger_io::cache;
 use hodeauxledger_io::cache::cache::Cache;
 use hodeauxledger_io::cache::rhex::retrieve_scope_rhex;
@@ -37,10 +37,14 @@ pub fn head_rhex(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Err
     Ok(vec![draft])
 }
 
-pub fn process_request_rhex(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
-    let response = match rhex.intent.record_type.as_str() {
-        "📩:R⬢" | "request:rhex" => request_rhex(rhex, first_time),
-        "📩:➡️🧬" | "request:head" => head_rhex(rhex, first_time),
+pub fn process_request_rhex(
+    rhex: &Rhex,
+    body: &RecordBody,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    let response = match body {
+        RecordBody::RequestRhex(_) => request_rhex(rhex, first_time),
+        RecordBody::RequestHead(_) => head_rhex(rhex, first_time),
         _ => Ok(Vec::new()),
     };
     if response.is_err() {}
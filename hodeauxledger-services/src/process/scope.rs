@@ -1,28 +1,36 @@
 use anyhow::Ok;
 use ed25519_dalek::VerifyingKey;
 use hodeauxledger_core::{
-    GTClock, Key, Rhex,
+    Key, Rhex, ScopeName,
     policy::{policy::Policy, rule::Rule},
+    record::{
+        body::RecordBody,
+        scope::{ScopeCreate, ScopeGenesis, ScopeRequest, ScopeSeal},
+    },
     scope::authority::Authority,
     to_base64,
 };
-use hodeauxledger_io::{Cache, cache};
+use hodeauxledger_io::{Cache, cache, clock, disk::config::NODE_CONFIG_PATH};
 
-use crate::rhex::builder;
+use crate::scope::scope::update_scope_in_table;
 
 /// Processes scope:genesis record. Neither states really does anything
 /// as this is just kind of a placeholder for start of a scope.
-pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
+pub fn genesis(
+    rhex: &Rhex,
+    body: &ScopeGenesis,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
     if first_time {
         println!("🌐💡 occurred for 🌐:{}", rhex.intent.scope);
     }
-    let unix_ms = &rhex.intent.data.get("unix_ms").and_then(|v| v.as_u64());
 
     let cache = Cache::connect("")?;
-    let clock = if &rhex.intent.scope == "" && unix_ms.is_some() {
-        GTClock::new(unix_ms.unwrap().into())
+    // The root genesis starts GT; everything after runs on its clock.
+    let clock = if rhex.intent.scope.is_empty() && body.unix_epoch_ms.is_some() {
+        clock::adopt_genesis(&cache.conn, NODE_CONFIG_PATH, rhex)?
     } else {
-        GTClock::new(1756876283931)
+        clock::gt_clock()?
     };
 
     cache::scopes::cache_scope(
@@ -32,21 +40,30 @@ pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error
         &clock.now_micromarks_u64(),
         &[0u8; 32],
     )?;
-    cache::authorities::cache_authority(
-        &cache.conn,
-        &rhex.intent.scope,
-        &vec![Authority {
+    // Named authorities run the scope; failing that, whoever made it does,
+    // until a 🔑:🟢 says otherwise.
+    let authorities = if body.authorities.is_empty() {
+        vec![Authority {
             name: "genesis".to_string(),
             host: "".to_string(),
             port: 0,
             proto: "rhex".to_string(),
             public_key: rhex.intent.author_public_key,
             priority: 0,
-        }],
-    )?;
-    let mut key = Key::new();
-    key.set_pub_key(VerifyingKey::from_bytes(&rhex.intent.author_public_key)?);
-    cache::key::cache_key(&cache.conn, &rhex.intent.scope, &key)?;
+        }]
+    } else {
+        for authority in &body.authorities {
+            authority.validate()?;
+        }
+        body.authorities.iter().map(|a| a.to_authority()).collect()
+    };
+    cache::authorities::cache_authority(&cache.conn, &rhex.intent.scope, &authorities)?;
+    for authority in &authorities {
+        let mut key = Key::new();
+        key.set_pub_key(VerifyingKey::from_bytes(&authority.public_key)?);
+        key.roles = Some(vec!["👑".to_string()]);
+        cache::key::cache_key(&cache.conn, &rhex.intent.scope, &key)?;
+    }
     cache::policies::cache_policy(
         &cache.conn,
         &rhex.intent.scope,
@@ -56,6 +73,9 @@ pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error
             quorum_ttl: None,
             effective_micromark: None,
             expiration_micromark: None,
+            max_record_bytes: None,
+            max_data_bytes: None,
+            inherit: true,
             scope: rhex.intent.scope.clone(),
             rules: vec![Rule {
                 record_type: "policy:set".to_string(),
@@ -63,9 +83,11 @@ pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error
                 append_roles: vec!["👑".to_string()],
                 quorum_k: 1,
                 quorum_roles: vec!["👑".to_string()],
+                deny: false,
             }],
         },
         &rhex.current_hash.unwrap(),
+        rhex.context.at,
     )?;
     cache::rules::cache_rule(
         &cache.conn,
@@ -75,6 +97,7 @@ pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error
             quorum_k: 1,
             quorum_roles: vec!["👑".to_string()],
             rate_per_mark: 80,
+            deny: false,
         },
         &rhex.intent.scope,
     )?;
@@ -83,76 +106,85 @@ pub fn genesis(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error
 
 /// Processes scope:create record. This is the actual record that designates
 /// a new child scope off the parent.
-pub fn create(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
-    let new_scope = rhex.intent.data.get("new_scope").and_then(|v| v.as_str());
-    if new_scope.is_none() {
-        println!(
-            "❌:🌐_🟢 occurred in 🌐:{}, missing new_scope ⬇️🧬:{}",
-            rhex.intent.scope,
-            to_base64(&rhex.current_hash.unwrap())
-        );
-        return Ok(Vec::new());
-    }
-
-    let new_scope = new_scope.unwrap();
+pub fn create(
+    rhex: &Rhex,
+    body: &ScopeCreate,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
     if first_time {
         println!(
             "🌐:🟢 occurred in 🌐:{} for 🌐:{}",
-            rhex.intent.scope, new_scope
+            rhex.intent.scope, body.new_scope
         );
     }
     Ok(Vec::new())
 }
 
-pub fn request(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
-    let new_scope = rhex.intent.data.get("new_scope").and_then(|v| v.as_str());
-    let genesis = rhex.intent.data.get("genesis").and_then(|v| v.as_object());
-
-    if new_scope.is_none() {
+pub fn request(
+    rhex: &Rhex,
+    body: &ScopeRequest,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    if body.genesis.is_none() {
         println!(
-            "❌:🌐_📩 occurred in 🌐:{}, missing new_scope ⬇️🧬:{}",
+            "❌:🌐_📩 occurred in 🌐:{}, missing genesis ⬇️🧬:{}",
             rhex.intent.scope,
             to_base64(&rhex.current_hash.unwrap())
         );
-        let sk = Key::new();
-        let out_rhex = builder::build_rhex(
-            &rhex.intent.previous_hash,
-            &rhex.intent.scope.as_str(),
-            &sk,
-            &rhex.intent.author_public_key,
-            "❌:🌐_📩",
-            rhex.intent.data.clone(),
+        return Ok(vec![rhex.clone()]);
+    }
+
+    // usherd turns valid requests into a 🌐:🟢 and the child's 🌐:💡, see
+    // scope::create. Nothing to do for one on its own.
+    if first_time {
+        println!(
+            "🌐:📩 occurred in 🌐:{} for 🌐:{}",
+            rhex.intent.scope, body.new_scope
         );
+    }
+    Ok(Vec::new())
+}
 
-        return Ok(vec![out_rhex]);
+/// Processes scope:seal, appended in the parent to close one of its
+/// children. The child and everything under it take no more appends.
+pub fn seal(rhex: &Rhex, body: &ScopeSeal, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
+    let scope = &rhex.intent.scope;
+    let seal_hash = rhex
+        .current_hash
+        .ok_or_else(|| anyhow::anyhow!("🌐:🔴 has no ⬇️🧬"))?;
+
+    let sealed = ScopeName::parse(&body.sealed_scope)?;
+    if sealed.parent().is_none_or(|p| p.as_str() != scope) {
+        anyhow::bail!("🌐:{} isn't a child of 🌐:{scope}", body.sealed_scope);
     }
-    if genesis.is_none() {
-        println!(
-            "❌:🌐_📩 occurred in 🌐:{}, missing genesis ⬇️🧬:{}",
-            rhex.intent.scope,
-            to_base64(&rhex.current_hash.unwrap())
+
+    let cache = Cache::connect("")?;
+    if let Some((earlier, _)) = cache::scopes::retrieve_seal(&cache.conn, &body.sealed_scope)? {
+        anyhow::bail!(
+            "🌐:{} is already sealed by {}",
+            body.sealed_scope,
+            to_base64(&earlier)
         );
-        return Ok(vec![rhex.clone()]);
     }
+    cache::scopes::seal_scope(&cache.conn, &body.sealed_scope, &seal_hash, rhex.context.at)?;
+    update_scope_in_table(&body.sealed_scope, |s| s.sealed = Some(seal_hash))?;
 
-    println!(
-        "🌐:📩 occurred in 🌐:{} for 🌐:{}",
-        rhex.intent.scope,
-        new_scope.unwrap()
-    );
     if first_time {
-        // Create scope on disk
-
-        // Add scope to scope_table
+        println!("🌐:🔴 occurred in 🌐:{scope} for 🌐:{}", body.sealed_scope);
     }
     Ok(Vec::new())
 }
 
-pub fn process_scope_rhex(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
-    match rhex.intent.record_type.as_str() {
-        "🌐:💡" | "scope:genesis" => genesis(rhex, first_time),
-        "🌐:🟢" | "scope:create" => create(rhex, first_time),
-        "🌐:📩" | "scope:request" => request(rhex, first_time),
+pub fn process_scope_rhex(
+    rhex: &Rhex,
+    body: &RecordBody,
+    first_time: bool,
+) -> Result<Vec<Rhex>, anyhow::Error> {
+    match body {
+        RecordBody::ScopeGenesis(b) => genesis(rhex, b, first_time),
+        RecordBody::ScopeCreate(b) => create(rhex, b, first_time),
+        RecordBody::ScopeRequest(b) => request(rhex, b, first_time),
+        RecordBody::ScopeSeal(b) => seal(rhex, b, first_time),
         _ => Ok(Vec::new()),
     }
 }
@@ -1,3 +1,5 @@
+use hodeauxledger_core::record::record_type::RecordType;
+use hodeauxledger_core::rhex::limits::RecordLimits;
 use hodeauxledger_core::{Intent, Key, Rhex, Signature};
 
 /// Builds a R⬢ using supplied data and author signs
@@ -6,6 +8,8 @@ use hodeauxledger_core::{Intent, Key, Rhex, Signature};
 /// * `usher_pk` - target usher's public key
 /// * `record_type` - R⬢ record type
 /// * `data` - JSON data payload
+///
+/// Fails if the result is already over the record size limits.
 pub fn build_rhex(
     previous_hash: &[u8; 32],
     scope: &str,
@@ -13,7 +17,12 @@ pub fn build_rhex(
     usher_pk: &[u8; 32],
     record_type: &str,
     data: serde_json::Value,
-) -> Rhex {
+) -> Result<Rhex, anyhow::Error> {
+    // Always hash the canonical 📄, whichever form we were handed.
+    let record_type = RecordType::parse(record_type)?;
+    if record_type.is_wildcard() {
+        anyhow::bail!("📄 {record_type} is a pattern, not a record type");
+    }
     let nonce = &Rhex::gen_nonce();
     let key = sk;
     let author_pk = key.to_bytes();
@@ -25,14 +34,14 @@ pub fn build_rhex(
         nonce,
         &author_pk,
         usher_pk,
-        record_type,
+        record_type.as_str(),
         data,
     );
     let mut rhex = Rhex::draft(intent);
 
     // Sign the intent
-    let author_hash = rhex.compute_content_hash().unwrap();
-    let signature = key.sign(&author_hash).unwrap();
+    let author_hash = rhex.compute_content_hash()?;
+    let signature = key.sign(&author_hash)?;
 
     // Push sig on stack
     let author_sig = Signature {
@@ -41,7 +50,8 @@ pub fn build_rhex(
         sig: signature.to_bytes(),
     };
     rhex.signatures.push(author_sig);
-    rhex
+    RecordLimits::for_rhex(&rhex)?.check(&rhex)?;
+    Ok(rhex)
 }
 
 pub fn usher_sign(rhex: &Rhex, at: u64, sk: [u8; 32]) -> Rhex {
@@ -1,8 +1,9 @@
+use hodeauxledger_core::record::record_type::RecordType;
 use hodeauxledger_io::cache::{cache::Cache, rhex::retrieve_scope_rhex, scopes::retrieve_scope};
 
 pub fn get_record_types_from_cache(
     scope: &str,
-    record_types: &Vec<String>,
+    record_types: &[String],
 ) -> Result<Vec<String>, anyhow::Error> {
     let cache = Cache::connect("")?;
     let scope_data = retrieve_scope_rhex(&cache.conn, scope);
@@ -11,12 +12,18 @@ pub fn get_record_types_from_cache(
     }
     let scope_data = scope_data.unwrap();
 
+    let patterns: Vec<RecordType> = record_types
+        .iter()
+        .map(|rt| RecordType::parse(rt))
+        .collect::<Result<_, _>>()?;
+
     let mut out = Vec::new();
     for rhex in scope_data {
-        for record_type in record_types {
-            if rhex.intent.record_type == *record_type {
-                out.push(rhex.intent.record_type.clone());
-            }
+        let Ok(record_type) = RecordType::parse(&rhex.intent.record_type) else {
+            continue;
+        };
+        if patterns.iter().any(|p| p.matches(&record_type)) {
+            out.push(rhex.intent.record_type.clone());
         }
     }
     Ok(out)
@@ -1,21 +1,41 @@
-use hodeauxledger_core::{Rhex, RhexUrl, rhex, schema::schema::Schema};
+use hodeauxledger_core::{Rhex, RhexUrl, record::body::RecordBody, schema::schema::Schema};
 
-use crate::process::{request, scope};
+use crate::process::{alias, authority, ban, key, policy, request, scope};
 pub fn process_rhex(rhex: &Rhex, first_time: bool) -> Vec<Rhex> {
-    let exploded_record_type = rhex.intent.record_type.split(":").collect::<Vec<&str>>();
-    let record_major = exploded_record_type[0];
+    match try_process_rhex(rhex, first_time) {
+        Ok(returned_rhex) => returned_rhex,
+        Err(e) => {
+            println!("❌ in 🌐:{}: {:#}", rhex.intent.scope, e);
+            Vec::new()
+        }
+    }
+}
 
-    let result = match record_major {
-        "🌐" | "scope" => scope::process_scope_rhex(rhex, first_time),
-        //"🔑" | "key" => {}
-        //"👑" | "authority" => {}
-        "📩" | "request" => request::process_request_rhex(rhex, first_time),
-        //"📦" | "record" => {}
+/// [`process_rhex`], handing back the error instead of printing it.
+pub fn try_process_rhex(rhex: &Rhex, first_time: bool) -> Result<Vec<Rhex>, anyhow::Error> {
+    let body = RecordBody::from_rhex(rhex)?;
+    match &body {
+        RecordBody::ScopeGenesis(_)
+        | RecordBody::ScopeRequest(_)
+        | RecordBody::ScopeCreate(_)
+        | RecordBody::ScopeSeal(_) => scope::process_scope_rhex(rhex, &body, first_time),
+        RecordBody::PolicySet(b) => policy::set(rhex, b, first_time),
+        RecordBody::KeyGrant(_) | RecordBody::KeyRevoke(_) => {
+            key::process_key_records(rhex, &body, first_time)
+        }
+        RecordBody::AliasGrant(_) | RecordBody::AliasRevoke(_) => {
+            alias::process_alias_records(rhex, &body, first_time)
+        }
+        RecordBody::RecordBan(b) => ban::ban(rhex, b, first_time),
+        RecordBody::AuthorityGrant(_) | RecordBody::AuthorityRevoke(_) => {
+            authority::process_authority_records(rhex, &body, first_time)
+        }
+        RecordBody::RequestRhex(_)
+        | RecordBody::RequestHead(_)
+        | RecordBody::RequestPolicy(_)
+        | RecordBody::RequestAlias(_) => request::process_request_rhex(rhex, &body, first_time),
         _ => Ok(Vec::new()),
-    };
-    if result.is_err() {}
-    let returned_rhex = result.unwrap();
-    returned_rhex
+    }
 }
 
 pub fn get_schema(rhex: &Rhex) -> Result<Schema, anyhow::Error> {
@@ -1,6 +1,401 @@
-use hodeauxledger_core::Rhex;
+use std::fmt;
 
-pub fn validate_rhex(rhex: &Rhex) -> bool {
-    let _ = rhex;
-    true
+use hodeauxledger_core::{
+    GtTime, Rhex, key::revocation::RevokeReason, record::record_type::RecordType, to_base64,
+};
+use hodeauxledger_io::{Cache, cache, clock::gt_clock};
+
+use crate::scope::{authorities::get_authorities_in, policy::resolve_in};
+
+/// Something wrong with a R⬢ that only shows given what the ledger knows:
+/// which keys were granted and when, and who may usher. [`Rhex::validate`]
+/// covers the record on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextIssue {
+    /// Author signature was made by a key other than ✍️🔓.
+    AuthorKeyMismatch {
+        signed_by: [u8; 32],
+    },
+    /// Usher signature was made by a key other than 📣🔓.
+    UsherKeyMismatch {
+        signed_by: [u8; 32],
+    },
+    /// ✍️🔓 was never granted in the 🌐.
+    AuthorNotGranted,
+    AuthorNotYetEffective {
+        effective: u64,
+        at: u64,
+    },
+    AuthorExpired {
+        expires: u64,
+        at: u64,
+    },
+    AuthorRevoked {
+        reason: RevokeReason,
+        revoked: u64,
+        at: u64,
+    },
+    /// 📣🔓 isn't an authority for the 🌐.
+    UsherNotAuthority,
+    /// No authorities are known for the 🌐, so its usher can't be checked.
+    NoAuthorities,
+}
+
+impl ContextIssue {
+    /// Stable, machine readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContextIssue::AuthorKeyMismatch { .. } => "author_key_mismatch",
+            ContextIssue::UsherKeyMismatch { .. } => "usher_key_mismatch",
+            ContextIssue::AuthorNotGranted => "author_not_granted",
+            ContextIssue::AuthorNotYetEffective { .. } => "author_not_yet_effective",
+            ContextIssue::AuthorExpired { .. } => "author_expired",
+            ContextIssue::AuthorRevoked { .. } => "author_revoked",
+            ContextIssue::UsherNotAuthority => "usher_not_authority",
+            ContextIssue::NoAuthorities => "no_authorities",
+        }
+    }
+}
+
+/// Does ✍️🔓 need a grant to have written this? Requests are open to
+/// anyone, a genesis is what grants its author, and policies may open a
+/// 📄 to everyone.
+fn needs_grant(cache: &Cache, rhex: &Rhex, at: u64) -> Result<bool, anyhow::Error> {
+    let Ok(rt) = RecordType::parse(&rhex.intent.record_type) else {
+        return Ok(true);
+    };
+    if rt.family() == "request" || rt.as_str() == "scope:genesis" {
+        return Ok(false);
+    }
+    let policy = resolve_in(cache, &rhex.intent.scope, Some(at))?.policy;
+    Ok(!policy.is_open(&rt))
+}
+
+/// Check `rhex` against the key and authority caches: its signatures were
+/// made by the keys its intent names, ✍️🔓 was granted in the 🌐 and in its
+/// window at ⏱️ and not revoked by then, and any usher is an authority
+/// for the 🌐. A submission no usher has signed has no ⏱️ yet, so it's
+/// judged as of our clock. Returns every issue found.
+pub fn validate_context(rhex: &Rhex) -> Result<Vec<ContextIssue>, anyhow::Error> {
+    let at = if rhex.signatures.iter().any(|s| s.sig_type == 1) {
+        rhex.context.at
+    } else {
+        gt_clock()?.now_micromarks_u64()
+    };
+    validate_context_in(&Cache::connect("")?, rhex, at)
+}
+
+/// [`validate_context`] against `cache`, judging ✍️🔓 as of micromark `at`.
+fn validate_context_in(
+    cache: &Cache,
+    rhex: &Rhex,
+    at: u64,
+) -> Result<Vec<ContextIssue>, anyhow::Error> {
+    let mut issues = Vec::new();
+    let scope = &rhex.intent.scope;
+
+    if let Some(author) = rhex.signatures.iter().find(|s| s.sig_type == 0)
+        && author.public_key != rhex.intent.author_public_key
+    {
+        issues.push(ContextIssue::AuthorKeyMismatch {
+            signed_by: author.public_key,
+        });
+    }
+    let usher = rhex.signatures.iter().find(|s| s.sig_type == 1);
+    if let Some(usher) = usher
+        && usher.public_key != rhex.intent.usher_public_key
+    {
+        issues.push(ContextIssue::UsherKeyMismatch {
+            signed_by: usher.public_key,
+        });
+    }
+
+    let key = cache::key::retrieve_key(&cache.conn, scope, &rhex.intent.author_public_key)?;
+    // A revoked key can't sign anything, open 📄 or not.
+    if let Some(r) = key
+        .as_ref()
+        .and_then(|k| k.revoked.as_ref())
+        .filter(|r| r.in_force(at))
+    {
+        issues.push(ContextIssue::AuthorRevoked {
+            reason: r.reason,
+            revoked: r.effective_micromark,
+            at,
+        });
+    }
+    if needs_grant(cache, rhex, at)? {
+        match key
+            .as_ref()
+            .filter(|k| k.roles.as_ref().is_some_and(|r| !r.is_empty()))
+        {
+            None => issues.push(ContextIssue::AuthorNotGranted),
+            Some(key) => {
+                if let Some(effective) = key.effective_micromark.filter(|&e| e > at) {
+                    issues.push(ContextIssue::AuthorNotYetEffective { effective, at });
+                }
+                if let Some(expires) = key.expires_micromark.filter(|&x| x != 0 && at >= x) {
+                    issues.push(ContextIssue::AuthorExpired { expires, at });
+                }
+            }
+        }
+    }
+
+    if usher.is_some() {
+        match get_authorities_in(cache, scope) {
+            Ok(authorities) if !authorities.is_empty() => {
+                if !authorities
+                    .iter()
+                    .any(|a| a.public_key == rhex.intent.usher_public_key)
+                {
+                    issues.push(ContextIssue::UsherNotAuthority);
+                }
+            }
+            _ => issues.push(ContextIssue::NoAuthorities),
+        }
+    }
+
+    Ok(issues)
+}
+
+impl fmt::Display for ContextIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextIssue::AuthorKeyMismatch { signed_by } => write!(
+                f,
+                "author signature is by {}, not ✍️🔓",
+                to_base64(signed_by)
+            ),
+            ContextIssue::UsherKeyMismatch { signed_by } => write!(
+                f,
+                "usher signature is by {}, not 📣🔓",
+                to_base64(signed_by)
+            ),
+            ContextIssue::AuthorNotGranted => write!(f, "✍️🔓 is not granted in this 🌐"),
+            ContextIssue::AuthorNotYetEffective { effective, at } => {
+                write!(
+                    f,
+                    "✍️🔓 not effective until {} (at {})",
+                    GtTime::from(*effective),
+                    GtTime::from(*at)
+                )
+            }
+            ContextIssue::AuthorExpired { expires, at } => {
+                write!(
+                    f,
+                    "✍️🔓 expired at {} (at {})",
+                    GtTime::from(*expires),
+                    GtTime::from(*at)
+                )
+            }
+            ContextIssue::AuthorRevoked {
+                reason,
+                revoked,
+                at,
+            } => write!(
+                f,
+                "✍️🔓 revoked ({reason}) at {} (at {})",
+                GtTime::from(*revoked),
+                GtTime::from(*at)
+            ),
+            ContextIssue::UsherNotAuthority => write!(f, "📣🔓 is not an authority for this 🌐"),
+            ContextIssue::NoAuthorities => write!(f, "no authorities known for this 🌐"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rhex::builder;
+    use hodeauxledger_core::{Key, key::revocation::Revocation, scope::authority::Authority};
+
+    struct Ledger {
+        cache: Cache,
+        author: Key,
+        usher: Key,
+    }
+
+    /// 🌐:a with `usher` as its authority and `author` granted ✍️ from 100
+    /// to 1000.
+    fn ledger() -> Ledger {
+        let cache = Cache::connect(":memory:").unwrap();
+        let conn = &cache.conn;
+        cache::key::build_table(conn).unwrap();
+        cache::policies::build_table(conn).unwrap();
+        cache::rules::build_table(conn).unwrap();
+        cache::authorities::build_table(conn).unwrap();
+
+        let usher = Key::generate();
+        let authority = Authority::new(
+            "usher".to_string(),
+            "localhost".to_string(),
+            1984,
+            "tcp".to_string(),
+            usher.to_bytes(),
+            0,
+        );
+        cache::authorities::cache_authority(conn, "a", &[authority]).unwrap();
+
+        let mut author = Key::generate();
+        author.roles = Some(vec!["✍️".to_string()]);
+        author.effective_micromark = Some(100);
+        author.expires_micromark = Some(1000);
+        cache::key::cache_key(conn, "a", &author).unwrap();
+        Ledger {
+            cache,
+            author,
+            usher,
+        }
+    }
+
+    /// Author-signed only, as a client submits it: no ⏱️ yet.
+    fn submission(author: &Key, usher: &Key) -> Rhex {
+        builder::build_rhex(
+            &[0u8; 32],
+            "a",
+            author,
+            &usher.to_bytes(),
+            "record:text",
+            serde_json::json!({}),
+        )
+        .unwrap()
+    }
+
+    fn record(author: &Key, usher: &Key, at: u64) -> Rhex {
+        let rhex = submission(author, usher);
+        builder::usher_sign(&rhex, at, usher.sk.as_ref().unwrap().to_bytes())
+    }
+
+    fn codes(ledger: &Ledger, rhex: &Rhex) -> Vec<&'static str> {
+        validate_context_in(&ledger.cache, rhex, rhex.context.at)
+            .unwrap()
+            .iter()
+            .map(|i| i.code())
+            .collect()
+    }
+
+    #[test]
+    fn clean_record_passes() {
+        let l = ledger();
+        assert!(codes(&l, &record(&l.author, &l.usher, 500)).is_empty());
+    }
+
+    #[test]
+    fn key_mismatch() {
+        let l = ledger();
+        let mut rhex = record(&l.author, &l.usher, 500);
+        rhex.signatures[0].public_key = Key::generate().to_bytes();
+        assert_eq!(codes(&l, &rhex), ["author_key_mismatch"]);
+
+        let mut rhex = record(&l.author, &l.usher, 500);
+        rhex.signatures[1].public_key = Key::generate().to_bytes();
+        assert_eq!(codes(&l, &rhex), ["usher_key_mismatch"]);
+    }
+
+    #[test]
+    fn not_granted() {
+        let l = ledger();
+        let stranger = Key::generate();
+        assert_eq!(
+            codes(&l, &record(&stranger, &l.usher, 500)),
+            ["author_not_granted"]
+        );
+    }
+
+    #[test]
+    fn grant_window() {
+        let l = ledger();
+        assert_eq!(
+            codes(&l, &record(&l.author, &l.usher, 99)),
+            ["author_not_yet_effective"]
+        );
+        assert!(codes(&l, &record(&l.author, &l.usher, 100)).is_empty());
+        assert!(codes(&l, &record(&l.author, &l.usher, 999)).is_empty());
+        assert_eq!(
+            codes(&l, &record(&l.author, &l.usher, 1000)),
+            ["author_expired"]
+        );
+    }
+
+    #[test]
+    fn revoked_by_then() {
+        let l = ledger();
+        let revocation = Revocation {
+            reason: RevokeReason::Superseded,
+            effective_micromark: 600,
+            compromised_micromark: None,
+            revoke_ref: None,
+        };
+        cache::key::revoke_key(&l.cache.conn, "a", &l.author.to_bytes(), &revocation).unwrap();
+        assert!(codes(&l, &record(&l.author, &l.usher, 599)).is_empty());
+        let issues = validate_context_in(&l.cache, &record(&l.author, &l.usher, 600), 600).unwrap();
+        assert_eq!(
+            issues,
+            [ContextIssue::AuthorRevoked {
+                reason: RevokeReason::Superseded,
+                revoked: 600,
+                at: 600,
+            }]
+        );
+    }
+
+    #[test]
+    fn usher_not_authority() {
+        let l = ledger();
+        let outsider = Key::generate();
+        assert_eq!(
+            codes(&l, &record(&l.author, &outsider, 500)),
+            ["usher_not_authority"]
+        );
+    }
+
+    #[test]
+    fn submissions_judged_when_received() {
+        let l = ledger();
+        let rhex = submission(&l.author, &l.usher);
+        assert_eq!(rhex.context.at, 0);
+        let codes_at = |at| -> Vec<&str> {
+            validate_context_in(&l.cache, &rhex, at)
+                .unwrap()
+                .iter()
+                .map(|i| i.code())
+                .collect()
+        };
+        // Granted from 100, so fine now even though ⏱️ is still 0.
+        assert!(codes_at(500).is_empty());
+        assert_eq!(codes_at(1200), ["author_expired"]);
+
+        let revocation = Revocation {
+            reason: RevokeReason::Retired,
+            effective_micromark: 300,
+            compromised_micromark: None,
+            revoke_ref: None,
+        };
+        cache::key::revoke_key(&l.cache.conn, "a", &l.author.to_bytes(), &revocation).unwrap();
+        assert_eq!(codes_at(500), ["author_revoked"]);
+    }
+
+    #[test]
+    fn regranted_key_signs_again() {
+        let l = ledger();
+        let pk = l.author.to_bytes();
+        let revocation = Revocation {
+            reason: RevokeReason::Superseded,
+            effective_micromark: 300,
+            compromised_micromark: None,
+            revoke_ref: None,
+        };
+        cache::key::revoke_key(&l.cache.conn, "a", &pk, &revocation).unwrap();
+        assert_eq!(
+            codes(&l, &record(&l.author, &l.usher, 500)),
+            ["author_revoked"]
+        );
+
+        let mut again = Key::new();
+        again.set_pub_key(l.author.pk.unwrap());
+        again.roles = Some(vec!["✍️".to_string()]);
+        again.effective_micromark = Some(400);
+        cache::key::cache_grant(&l.cache.conn, "a", &again, Some(&[4u8; 32])).unwrap();
+        assert!(codes(&l, &record(&l.author, &l.usher, 500)).is_empty());
+    }
 }
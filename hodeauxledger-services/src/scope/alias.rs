@@ -0,0 +1,30 @@
+use anyhow::{Result, anyhow};
+use hodeauxledger_core::{RhexUrl, to_base64};
+use hodeauxledger_io::{Cache, cache};
+
+/// rhex://scope/name → rhex://scope/<⬇️🧬>, keeping @version and #field.
+/// URLs that already carry a hash come back as they are.
+pub fn resolve(url: &RhexUrl) -> Result<RhexUrl> {
+    if url.hash().is_some() {
+        return Ok(url.clone());
+    }
+    let cache = Cache::connect("")?;
+    let alias = cache::aliases::retrieve_alias(&cache.conn, &url.scope, &url.hash_alias)?
+        .ok_or_else(|| anyhow!("no 🅰️ {} in 🌐:{}", url.hash_alias, url.scope))?;
+    Ok(url.with_hash_alias(&to_base64(&alias.hash)))
+}
+
+/// rhex://scope/<⬇️🧬> → rhex://scope/name, using the first alias by name.
+/// Hashes nobody has named, and URLs already using a name, come back as
+/// they are.
+pub fn unresolve(url: &RhexUrl) -> Result<RhexUrl> {
+    let Some(hash) = url.hash() else {
+        return Ok(url.clone());
+    };
+    let cache = Cache::connect("")?;
+    let aliases = cache::aliases::retrieve_aliases_for_hash(&cache.conn, &url.scope, &hash)?;
+    Ok(match aliases.first() {
+        Some(alias) => url.with_hash_alias(&alias.name),
+        None => url.clone(),
+    })
+}
@@ -1,14 +1,81 @@
-use hodeauxledger_core::Key;
+use hodeauxledger_core::{
+    GtTime, Rhex,
+    policy::{
+        decision::{AppendDecision, DenyReason},
+        policy::Policy,
+    },
+    record::record_type::RecordType,
+    time::skew::{TimeIssue, check_after_head},
+};
 use hodeauxledger_io::{Cache, cache};
 
-pub fn can_append(scope: &str, record_type: &str, key: &Key) -> Result<bool, anyhow::Error> {
-    // Get the policy for the scope
+use super::{policy::effective_policy_at, seal::sealed_by};
+
+/// Do append rules apply to `record_type` at all? Requests are only read,
+/// never appended.
+pub fn is_appendable(record_type: &str) -> bool {
+    !RecordType::parse(record_type).is_ok_and(|rt| rt.family() == "request")
+}
+
+/// Checks the scope's effective 📜 as of micromark `at` to see whether
+/// `author_public_key` may append a `record_type` record then. Nothing may
+/// be appended once the scope or one above it is sealed.
+pub fn can_append(
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+    at: u64,
+) -> Result<AppendDecision, anyhow::Error> {
+    let policy = effective_policy_at(scope, at)?.policy;
+    can_append_under(&policy, scope, record_type, author_public_key, at)
+}
+
+/// [`can_append`] with the 📜 already resolved for `at`, for callers that
+/// use the same 📜 for other checks too.
+pub fn can_append_under(
+    policy: &Policy,
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+    at: u64,
+) -> Result<AppendDecision, anyhow::Error> {
+    if let Some((sealed, seal)) = sealed_by(scope, at)? {
+        return Ok(AppendDecision::Deny(DenyReason::ScopeSealed {
+            scope: sealed,
+            seal,
+        }));
+    }
     let cache = Cache::connect("")?;
-    cache::policies::retrieve_policy(&cache.conn, &scope);
+    let key = cache::key::retrieve_key(&cache.conn, scope, author_public_key)?;
+    Ok(policy.evaluate_append(record_type, key.as_ref(), at))
+}
 
-    // Figure out if this key is able to append this record_type at all
+/// ⏱️ on `rhex` has to be after the head of its scope. The outer error is
+/// the cache failing; the inner one the record being out of order.
+pub fn after_head(rhex: &Rhex) -> Result<Result<(), TimeIssue>, anyhow::Error> {
+    let cache = Cache::connect("")?;
+    let head_at = cache::rhex::retrieve_latest_at(&cache.conn, &rhex.intent.scope)?;
+    Ok(check_after_head(
+        GtTime::from(rhex.context.at),
+        head_at.map(GtTime::from),
+    ))
+}
 
-    // Do we meet the requirements?
+/// Cache a record we accepted and processed, so the next [`after_head`] for
+/// its scope sees it as the head. Caching it again changes nothing.
+pub fn cache_accepted(rhex: &Rhex) -> Result<(), anyhow::Error> {
+    let cache = Cache::connect("")?;
+    cache::rhex::cache_rhex(&cache.conn, rhex)?;
+    Ok(())
+}
 
-    Ok(true)
+/// Judge a record already on the chain against the 📜 in force when it
+/// was appended.
+pub fn was_appendable(rhex: &Rhex) -> Result<AppendDecision, anyhow::Error> {
+    can_append(
+        &rhex.intent.scope,
+        &rhex.intent.record_type,
+        &rhex.intent.author_public_key,
+        rhex.context.at,
+    )
 }
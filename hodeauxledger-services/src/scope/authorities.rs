@@ -1,34 +1,37 @@
-use hodeauxledger_core::{Key, scope::authority::Authority};
+use hodeauxledger_core::{
+    policy::effective::lineage,
+    scope::{authority::Authority, scope::Scope},
+};
 use hodeauxledger_io::Cache;
 use hodeauxledger_io::cache;
 use hodeauxledger_io::disk;
 
-pub fn get_authorities(scope: &str, key: &Key) -> Result<Vec<Authority>, anyhow::Error> {
-    let cache = Cache::connect("")?;
-    let authorities = cache::authorities::retrieve_authorities(&cache.conn, scope)?;
-    if authorities.len() > 0 {
-        return Ok(authorities);
-    }
-    // Ok, we had zero authorties in the scope, so let's go find the
-    // scope and get the authorities
-    let scope_parts = &scope.split(".");
-    let mut scope_parts = scope_parts.clone().collect::<Vec<&str>>();
-    if scope_parts.len() > 1 {
-        scope_parts.pop();
-        let scope = scope_parts.join(".");
-        let authorities = get_authorities(&scope, key)?;
-        return Ok(authorities);
-    }
-    if scope_parts.len() == 1 {
-        let authorities = get_authorities(&scope_parts[0], key)?;
-        if authorities.len() > 0 {
-            return Ok(authorities);
-        } else {
-            let authorities = get_root_authorities()?;
+/// Authorities for `scope`: its own cached ones, or failing that the
+/// nearest parent's, or failing that the root authorities on disk.
+pub fn get_authorities(scope: &str) -> Result<Vec<Authority>, anyhow::Error> {
+    get_authorities_in(&Cache::connect("")?, scope)
+}
+
+/// [`get_authorities`] against `cache` rather than the node's own.
+pub(crate) fn get_authorities_in(
+    cache: &Cache,
+    scope: &str,
+) -> Result<Vec<Authority>, anyhow::Error> {
+    for name in lineage(scope).iter().rev() {
+        let authorities = cache::authorities::retrieve_authorities(&cache.conn, name)?;
+        if !authorities.is_empty() {
             return Ok(authorities);
         }
-    };
-    Ok(Vec::new())
+    }
+    get_root_authorities()
+}
+
+/// Pick up to `k` of `scope`'s authorities, weighted by priority, for a
+/// client to talk to.
+pub fn pick_authorities(scope: &str, k: usize) -> Result<Vec<Authority>, anyhow::Error> {
+    let mut s = Scope::new(scope, "client");
+    s.authorities = get_authorities(scope)?;
+    Ok(s.get_authorities_weighted(k))
 }
 
 pub fn get_root_authorities() -> Result<Vec<Authority>, anyhow::Error> {
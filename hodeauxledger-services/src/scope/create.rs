@@ -0,0 +1,301 @@
+use std::{fs, path::Path};
+
+use anyhow::{Result, anyhow, bail};
+use hodeauxledger_core::{
+    GtTime, Key, Rhex, from_base64,
+    record::{
+        body::Payload,
+        scope::{ScopeCreate, ScopeGenesis, ScopeRequest},
+    },
+    scope::{scope::Scope, table::ScopeTable},
+    time::skew::check_after_head,
+    to_base64,
+};
+use hodeauxledger_io::{
+    Cache, cache,
+    disk::{journal, rhex as diskrhex, scope as diskscope},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::rhex::{builder, process::process_rhex};
+
+/// Everything a scope:request turns into, journaled before any of it
+/// touches the ledger. Records are kept packed so they come back
+/// byte-for-byte.
+#[derive(Debug, Serialize, Deserialize)]
+struct ScopeCreation {
+    parent: String,
+    /// Packed 🌐:🟢 for the parent.
+    create: String,
+    /// Packed 🌐:💡 for the child.
+    genesis: String,
+    /// The child's entry in the scope table.
+    entry: Scope,
+}
+
+impl ScopeCreation {
+    fn records(&self) -> Result<(Rhex, Rhex)> {
+        let unpack = |s: &str| -> Result<Rhex> { Ok(Rhex::unpack(&from_base64(s)?)?) };
+        Ok((unpack(&self.create)?, unpack(&self.genesis)?))
+    }
+}
+
+/// Author + usher sign as `hot_key` and finalize.
+fn sign(
+    hot_key: &Key,
+    previous_hash: &[u8; 32],
+    scope: &str,
+    record_type: &str,
+    data: serde_json::Value,
+    at: u64,
+) -> Result<Rhex> {
+    let sk = hot_key
+        .sk
+        .as_ref()
+        .ok_or_else(|| anyhow!("usher 🔑 can't sign"))?
+        .to_bytes();
+    let rhex = builder::build_rhex(
+        previous_hash,
+        scope,
+        hot_key,
+        &hot_key.to_bytes(),
+        record_type,
+        data,
+    )?;
+    builder::usher_sign(&rhex, at, sk).finalize()
+}
+
+/// Turns a scope:request into a 🌐:🟢 appended to the parent and a 🌐:💡
+/// starting the child, signed by us. Either all of it lands (records on
+/// disk, child in the scope table) or, after a crash, the rest is finished
+/// by [`recover_scope_creations`]. Returns the two records for the
+/// requester.
+pub fn create_child_scope(
+    ledger_path: &str,
+    hot_key: &Key,
+    rhex: &Rhex,
+    body: &ScopeRequest,
+    at: u64,
+) -> Result<Vec<Rhex>> {
+    let parent = &rhex.intent.scope;
+    body.validate(parent)?;
+    let child = &body.new_scope;
+
+    let table = load_table(ledger_path)?;
+    if table.lookup(child).is_some()
+        || diskscope::record_path(ledger_path, child, &[0u8; 32])?.exists()
+    {
+        bail!("🌐:{child} already exists");
+    }
+
+    let create = ScopeCreate {
+        schema: Some("rhex://schema/scope_create@0".to_string()),
+        note: body.note.clone(),
+        new_scope: child.clone(),
+        authorities: body.authorities.clone(),
+    };
+    // We stamp ⏱️ here, so it's on us to keep it after the parent's head.
+    let cache = Cache::connect("")?;
+    let (parent_head, head_at) = cache::rhex::retrieve_head(&cache.conn, parent)?
+        .ok_or_else(|| anyhow!("🌐:{parent} has no head"))?;
+    check_after_head(GtTime::from(at), Some(GtTime::from(head_at)))
+        .map_err(|issue| anyhow!("🌐:{parent} {issue}"))?;
+    let create = sign(
+        hot_key,
+        &parent_head,
+        parent,
+        "scope:create",
+        create.to_data()?,
+        at,
+    )?;
+
+    let genesis = ScopeGenesis {
+        schema: Some("rhex://schema/scope_genesis@0".to_string()),
+        authorities: body.authorities.clone(),
+        ..body.genesis.clone().unwrap_or_default()
+    };
+    let genesis = sign(
+        hot_key,
+        &[0u8; 32],
+        child,
+        "scope:genesis",
+        genesis.to_data()?,
+        at,
+    )?;
+
+    let authorities: Vec<_> = body.authorities.iter().map(|a| a.to_authority()).collect();
+    let our_pk = hot_key.to_bytes();
+    let role = if authorities.iter().any(|a| a.public_key == our_pk) {
+        "authority"
+    } else {
+        "mirror"
+    };
+    let mut entry = Scope::new(child, role);
+    entry.authorities = authorities;
+    entry.head = genesis.current_hash()?;
+    entry.policy.scope = child.clone();
+
+    let creation = ScopeCreation {
+        parent: parent.clone(),
+        create: to_base64(&create.pack()?),
+        genesis: to_base64(&genesis.pack()?),
+        entry,
+    };
+    let id = diskscope::to_hex(&genesis.current_hash()?);
+    journal::write_journal(ledger_path, &id, &serde_json::to_value(&creation)?)?;
+    apply(ledger_path, &creation)?;
+    journal::remove_journal(ledger_path, &id)?;
+
+    // The ledger is settled; bring the cache up to date.
+    for record in [&create, &genesis] {
+        cache::rhex::cache_rhex(&cache.conn, record)?;
+        process_rhex(record, true);
+    }
+    Ok(vec![create, genesis])
+}
+
+/// Finish scope creations a crash left half done. Run before the ledger
+/// is read.
+pub fn recover_scope_creations(ledger_path: &str) -> Result<usize> {
+    let pending = journal::list_journals(ledger_path)?;
+    for (id, entry) in &pending {
+        let creation: ScopeCreation = serde_json::from_value(entry.clone())?;
+        println!("🌐 finishing creation of 🌐:{}", creation.entry.name);
+        apply(ledger_path, &creation)?;
+        journal::remove_journal(ledger_path, id)?;
+    }
+    Ok(pending.len())
+}
+
+/// Each step checks whether it already happened, so this can be run again
+/// after being cut short.
+fn apply(ledger_path: &str, creation: &ScopeCreation) -> Result<()> {
+    let (create, genesis) = creation.records()?;
+    write_once(
+        &diskscope::record_path(ledger_path, &creation.parent, &create.intent.previous_hash)?,
+        &create,
+    )?;
+    let child_dir = Path::new(ledger_path).join(&creation.entry.name);
+    fs::create_dir_all(&child_dir)?;
+    write_once(
+        &diskscope::record_path(ledger_path, &creation.entry.name, &[0u8; 32])?,
+        &genesis,
+    )?;
+
+    let mut table = load_table(ledger_path)?;
+    let create_hash = create.current_hash()?;
+    table.update(&creation.parent, |parent| {
+        if parent.head == create.intent.previous_hash {
+            parent.head = create_hash;
+        }
+    });
+    if table.lookup(&creation.entry.name).is_none() {
+        table.insert(creation.entry.clone())?;
+    }
+    diskscope::save_scope_table(ledger_path, &table)?;
+    Ok(())
+}
+
+/// Write `rhex` at `path` unless it's already there. Anything else at that
+/// spot means the chain moved on without us.
+fn write_once(path: &Path, rhex: &Rhex) -> Result<()> {
+    let path = path.to_path_buf();
+    if path.exists() {
+        let existing = diskrhex::load_rhex(&path)?;
+        if existing.current_hash != rhex.current_hash {
+            bail!("{:?} already holds another record", path);
+        }
+        return Ok(());
+    }
+    diskrhex::save_rhex(&path, rhex)?;
+    Ok(())
+}
+
+fn load_table(ledger_path: &str) -> Result<ScopeTable> {
+    ScopeTable::from_json(serde_json::from_str(&diskscope::load_scope_table(
+        ledger_path,
+    )?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovery_finishes_a_journaled_creation() {
+        let ledger = std::env::temp_dir().join(format!("hl-create-{}", std::process::id()));
+        let ledger_path = ledger.to_str().unwrap();
+        fs::create_dir_all(ledger.join("a")).unwrap();
+        let key = Key::generate();
+
+        let root_genesis = sign(
+            &key,
+            &[0u8; 32],
+            "a",
+            "scope:genesis",
+            serde_json::json!({}),
+            1,
+        )
+        .unwrap();
+        diskrhex::save_rhex(
+            &diskscope::record_path(ledger_path, "a", &[0u8; 32]).unwrap(),
+            &root_genesis,
+        )
+        .unwrap();
+        let mut parent = Scope::new("a", "authority");
+        parent.head = root_genesis.current_hash.unwrap();
+        diskscope::save_scope_table(ledger_path, &ScopeTable::new(vec![parent]).unwrap()).unwrap();
+
+        let create = sign(
+            &key,
+            &root_genesis.current_hash.unwrap(),
+            "a",
+            "scope:create",
+            serde_json::json!({ "new_scope": "a.b", "👑": [] }),
+            2,
+        )
+        .unwrap();
+        let genesis = sign(
+            &key,
+            &[0u8; 32],
+            "a.b",
+            "scope:genesis",
+            serde_json::json!({}),
+            2,
+        )
+        .unwrap();
+        let creation = ScopeCreation {
+            parent: "a".to_string(),
+            create: to_base64(&create.pack().unwrap()),
+            genesis: to_base64(&genesis.pack().unwrap()),
+            entry: Scope::new("a.b", "mirror"),
+        };
+        // Crash after the parent's record landed, before anything else.
+        journal::write_journal(ledger_path, "x", &serde_json::to_value(&creation).unwrap())
+            .unwrap();
+        write_once(
+            &diskscope::record_path(ledger_path, "a", &create.intent.previous_hash).unwrap(),
+            &create,
+        )
+        .unwrap();
+
+        assert_eq!(recover_scope_creations(ledger_path).unwrap(), 1);
+        assert_eq!(recover_scope_creations(ledger_path).unwrap(), 0);
+        assert_eq!(
+            diskscope::scope_head(ledger_path, "a").unwrap(),
+            create.current_hash.unwrap()
+        );
+        assert_eq!(
+            diskscope::scope_head(ledger_path, "a.b").unwrap(),
+            genesis.current_hash.unwrap()
+        );
+        let table = load_table(ledger_path).unwrap();
+        assert!(table.lookup("a.b").is_some());
+        assert_eq!(
+            table.lookup("a").unwrap().head,
+            create.current_hash.unwrap()
+        );
+
+        fs::remove_dir_all(&ledger).unwrap();
+    }
+}
@@ -1,4 +1,9 @@
+pub mod alias;
 pub mod append;
 pub mod authorities;
+pub mod create;
 pub mod head;
+pub mod policy;
+pub mod rate;
 pub mod scope;
+pub mod seal;
@@ -0,0 +1,86 @@
+use hodeauxledger_core::policy::{
+    effective::{EffectivePolicy, lineage},
+    policy::Policy,
+};
+use hodeauxledger_io::{Cache, cache};
+
+/// The 📜 in force for `scope`: its own cached policy merged with every
+/// parent's (see [`EffectivePolicy::resolve`]). If no scope up the tree
+/// sets 🧱 defaults, the built-in ones apply.
+pub fn effective_policy(scope: &str) -> Result<EffectivePolicy, anyhow::Error> {
+    resolve(scope, None)
+}
+
+/// Like [`effective_policy`], but with each scope's 📜 as it stood at
+/// micromark `at`.
+pub fn effective_policy_at(scope: &str, at: u64) -> Result<EffectivePolicy, anyhow::Error> {
+    resolve(scope, Some(at))
+}
+
+fn resolve(scope: &str, at: Option<u64>) -> Result<EffectivePolicy, anyhow::Error> {
+    resolve_in(&Cache::connect("")?, scope, at)
+}
+
+/// [`resolve`] against `cache` rather than the node's own.
+pub(crate) fn resolve_in(
+    cache: &Cache,
+    scope: &str,
+    at: Option<u64>,
+) -> Result<EffectivePolicy, anyhow::Error> {
+    let conn = &cache.conn;
+    let mut policies = Vec::new();
+    for name in lineage(scope) {
+        let policy = match at {
+            // The rules table only knows the latest 📜, so it can't say
+            // what was in force back then. No 📜 then means none.
+            Some(at) => match cache::policies::policy_at(conn, &name, at)? {
+                Some(p) => p,
+                None => continue,
+            },
+            // Older caches only kept the latest rules, in their own table.
+            None => match cache::policies::retrieve_policy(conn, &name)? {
+                Some(p) if !p.rules.is_empty() => p,
+                other => {
+                    let mut p = other.unwrap_or_else(|| Policy::new(&name, Vec::new()));
+                    p.rules = cache::rules::retrieve_rules(conn, &name)?;
+                    p
+                }
+            },
+        };
+        policies.push(policy);
+    }
+    let mut effective = EffectivePolicy::resolve(scope, &policies);
+    if effective.policy.defaults.is_none() {
+        effective.policy.defaults = Policy::default().defaults;
+    }
+    Ok(effective)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hodeauxledger_core::policy::rule::Rule;
+
+    #[test]
+    fn earlier_records_skip_later_rules() {
+        let cache = Cache::connect(":memory:").unwrap();
+        let conn = &cache.conn;
+        cache::policies::build_table(conn).unwrap();
+        cache::rules::build_table(conn).unwrap();
+
+        let mut rule = Rule::new("record:text", &["👑"], 1, 10);
+        rule.deny = true;
+        let policy = Policy::new("a", vec![rule]);
+        cache::policies::cache_policy(conn, "a", &policy, &[1u8; 32], 100).unwrap();
+        cache::rules::replace_rules(conn, "a", &policy.rules).unwrap();
+
+        let before = resolve_in(&cache, "a", Some(50)).unwrap();
+        assert!(before.rules().all(|(r, _)| r.record_type != "record:text"));
+        let after = resolve_in(&cache, "a", Some(150)).unwrap();
+        assert!(
+            after
+                .rules()
+                .any(|(r, _)| r.record_type == "record:text" && r.deny)
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use hodeauxledger_core::{
+    policy::{policy::Policy, rate::RateDecision},
+    record::record_type::RecordType,
+};
+use hodeauxledger_io::{Cache, cache};
+
+/// Count an append by `author_public_key` against the ↔️ of the rule for
+/// `record_type` in `policy`, the 📜 in force for `scope` at `at`.
+pub fn take_rate(
+    policy: &Policy,
+    scope: &str,
+    record_type: &str,
+    author_public_key: &[u8; 32],
+    at: u64,
+) -> Result<RateDecision, anyhow::Error> {
+    let mut cache = Cache::connect("")?;
+    cache::rate_limits::build_table(&cache.conn)?;
+
+    // Count under the canonical 📄 so 🌐:💡 and scope:genesis share a bucket.
+    let rt = RecordType::parse(record_type)?;
+    let Some(rate_per_mark) = policy.rate_per_mark(&rt) else {
+        return Ok(RateDecision::Allow {
+            remaining: u64::MAX,
+        });
+    };
+
+    Ok(cache::rate_limits::take_rate(
+        &mut cache.conn,
+        scope,
+        rt.as_str(),
+        author_public_key,
+        rate_per_mark,
+        at,
+    )?)
+}
@@ -1,44 +1,58 @@
 use anyhow::Result;
 use hodeauxledger_core::{
     Rhex,
+    rhex::chain::verify_chain,
     scope::{scope::Scope, table::ScopeTable},
 };
-use hodeauxledger_io::disk::scope as diskscope;
+use hodeauxledger_io::{
+    cache::{cache::Cache, rhex::cache_rhex},
+    disk::scope as diskscope,
+};
+
+use super::append::{is_appendable, was_appendable};
+use crate::rhex::validator::validate_context;
 
 pub fn get_scope_table() -> Result<ScopeTable, anyhow::Error> {
     // Load the scope table to see which scopes we need to take care
     // of.
     println!("Loading 🌐 table...");
     let scope_table_raw = diskscope::load_scope_table("./data/ledger")?;
-    let scope_table: Vec<Scope> = serde_json::from_str(&scope_table_raw)?;
-    let mut out = Vec::new();
-    for scope in scope_table {
-        out.push(scope);
-    }
-    let out = ScopeTable::new(out);
-
-    Ok(out)
+    ScopeTable::from_json(serde_json::from_str(&scope_table_raw)?)
 }
 
 pub fn save_scope_table(st: &ScopeTable) -> Result<(), anyhow::Error> {
-    diskscope::save_scope_table("/ledger", st)?;
+    diskscope::save_scope_table("./data/ledger", st)?;
     Ok(())
 }
 
 pub fn add_scope_to_table(scope: &Scope) -> Result<(), anyhow::Error> {
     let mut st = get_scope_table()?;
-    st.scopes.push(scope.clone());
+    st.insert(scope.clone())?;
     save_scope_table(&st)?;
     Ok(())
 }
 
 pub fn remove_scope_from_table(scope_name: &str) -> Result<(), anyhow::Error> {
     let mut st = get_scope_table()?;
-    st.remove_scope(scope_name);
+    st.remove_scope(scope_name)?;
     save_scope_table(&st)?;
     Ok(())
 }
 
+/// Apply `update` to `scope_name`'s entry in the scope table and save it.
+/// Returns false, leaving the table alone, if we don't carry the scope.
+pub fn update_scope_in_table(
+    scope_name: &str,
+    update: impl FnOnce(&mut Scope),
+) -> Result<bool, anyhow::Error> {
+    let mut st = get_scope_table()?;
+    if !st.update(scope_name, update) {
+        return Ok(false);
+    }
+    save_scope_table(&st)?;
+    Ok(true)
+}
+
 pub fn scope_from_disk_to_cache(
     scope_name: &str,
     process_rhex: bool,
@@ -48,12 +62,46 @@ pub fn scope_from_disk_to_cache(
     if scope.is_none() {
         return Err(anyhow::anyhow!("scope not found"));
     }
-    let scope_data =
-        diskscope::load_scope("./data/ledger", scope_name, diskscope::ScopeSink::Both)?;
+    let mut scope_data =
+        diskscope::load_scope("./data/ledger", scope_name, diskscope::ScopeSink::Vec)?;
+
+    // Only the part of the chain before the first problem makes it into
+    // the cache.
+    let report = verify_chain(scope_name, &scope_data);
+    if let Some(bad) = report.first_bad_index() {
+        print!("{}", report);
+        scope_data.truncate(bad);
+    }
+    let cache = Cache::connect("")?;
     let mut output = Vec::new();
-    if process_rhex {
-        for rhex in scope_data {
-            output.extend(crate::rhex::process::process_rhex(&rhex, false));
+    for (index, rhex) in scope_data.iter().enumerate() {
+        // Each record is judged by the 🔑, authorities and 📜 of its own
+        // time, which the records before it have just put in the cache.
+        if process_rhex {
+            let issues = validate_context(rhex)?;
+            if !issues.is_empty() {
+                for issue in &issues {
+                    println!("❌ 🌐:{scope_name} #{index} [{}]: {issue}", issue.code());
+                }
+                println!("❌ 🌐:{scope_name} stopping replay at #{index}");
+                break;
+            }
+        }
+        // The genesis is what sets up the first 📜, so it can't be judged
+        // by one.
+        if process_rhex && index > 0 && is_appendable(&rhex.intent.record_type) {
+            let decision = was_appendable(rhex)?;
+            if !decision.is_allowed() {
+                println!(
+                    "❌ 🌐:{} #{index} {}: append {decision}, stopping replay",
+                    scope_name, rhex.intent.record_type
+                );
+                break;
+            }
+        }
+        cache_rhex(&cache.conn, rhex)?;
+        if process_rhex {
+            output.extend(crate::rhex::process::process_rhex(rhex, false));
         }
     }
 
@@ -62,7 +110,7 @@ pub fn scope_from_disk_to_cache(
 
 pub fn bootstrap_rhex_cache() -> Result<(), anyhow::Error> {
     let scope_table = get_scope_table()?;
-    for scope in scope_table.scopes {
+    for scope in scope_table.subtree("") {
         scope_from_disk_to_cache(scope.name.as_str(), true)?;
     }
     Ok(())
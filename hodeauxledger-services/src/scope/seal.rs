@@ -0,0 +1,42 @@
+use hodeauxledger_core::policy::effective::lineage;
+use hodeauxledger_io::{Cache, cache};
+
+/// The nearest 🌐 at or above `scope` sealed as of micromark `at`, with the
+/// ⬇️🧬 of its scope:seal. A seal closes the scope and everything under it.
+pub fn sealed_by(scope: &str, at: u64) -> Result<Option<(String, [u8; 32])>, anyhow::Error> {
+    sealed_by_in(&Cache::connect("")?, scope, at)
+}
+
+fn sealed_by_in(
+    cache: &Cache,
+    scope: &str,
+    at: u64,
+) -> Result<Option<(String, [u8; 32])>, anyhow::Error> {
+    for name in lineage(scope).into_iter().rev() {
+        if let Some((seal, sealed_at)) = cache::scopes::retrieve_seal(&cache.conn, &name)?
+            && sealed_at <= at
+        {
+            return Ok(Some((name, seal)));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seals_close_the_subtree() {
+        let cache = Cache::connect(":memory:").unwrap();
+        cache::scopes::build_table(&cache.conn).unwrap();
+        cache::scopes::seal_scope(&cache.conn, "a.b", &[1u8; 32], 100).unwrap();
+
+        let sealed = |scope, at| sealed_by_in(&cache, scope, at).unwrap();
+        assert_eq!(sealed("a.b.c", 99), None);
+        assert_eq!(sealed("a.b", 100), Some(("a.b".to_string(), [1u8; 32])));
+        assert_eq!(sealed("a.b.c", 150), Some(("a.b".to_string(), [1u8; 32])));
+        assert_eq!(sealed("a", 150), None);
+        assert_eq!(sealed("a.c", 150), None);
+    }
+}
@@ -36,6 +36,12 @@ pub enum Command {
 
     // Encrypt hot key
     Encrypt(EncryptArgs),
+
+    // Seal a R⬢'s data to recipients
+    EncryptData(EncryptDataArgs),
+
+    // Open a sealed R⬢'s data
+    DecryptData(DecryptDataArgs),
 }
 
 #[derive(Args, Debug)]
@@ -123,3 +129,32 @@ pub struct EncryptArgs {
     #[arg(short, long)]
     pub output: String,
 }
+
+#[derive(Args, Debug)]
+pub struct EncryptDataArgs {
+    /// R⬢ to seal (unsigned)
+    #[arg(short, long)]
+    pub input: String,
+
+    /// R⬢ to ouput
+    #[arg(short, long)]
+    pub output: String,
+
+    /// 🔓 recipient public key (base64), repeat for more
+    #[arg(short, long, required = true)]
+    pub recipient: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DecryptDataArgs {
+    #[command(flatten)]
+    pub keys: KeyOpts,
+
+    /// R⬢ to open
+    #[arg(long)]
+    pub input: String,
+
+    /// 📊 JSON file to save, prints to stdout if missing
+    #[arg(long)]
+    pub output: Option<String>,
+}
@@ -5,6 +5,7 @@ use crate::argv::Command;
 
 mod argv;
 mod crypto;
+mod seal;
 mod sign;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -31,6 +32,8 @@ fn main() -> anyhow::Result<()> {
         Command::Verify(verify_args) => sign::verify(verify_args, args.verbose, args.quiet)?,
         Command::Hot(hot_args) => crypto::hot(hot_args, args.verbose, args.quiet)?,
         Command::Encrypt(encrypt_args) => crypto::encrypt(encrypt_args, args.verbose, args.quiet)?,
+        Command::EncryptData(seal_args) => seal::encrypt_data(seal_args, args.verbose, args.quiet)?,
+        Command::DecryptData(open_args) => seal::decrypt_data(open_args, args.verbose, args.quiet)?,
     };
     Ok(())
 }
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use anyhow::Result;
+use hodeauxledger_core::Key;
+use hodeauxledger_core::crypto::b64::from_base64_to_32;
+use hodeauxledger_io::disk::rhex as diskrhex;
+
+use crate::argv::{DecryptDataArgs, EncryptDataArgs};
+use crate::crypto;
+
+pub fn encrypt_data(
+    args: EncryptDataArgs,
+    verbose: bool,
+    quiet: bool,
+) -> Result<(), anyhow::Error> {
+    let recipients = args
+        .recipient
+        .iter()
+        .map(|r| from_base64_to_32(r.trim_start_matches('\\')))
+        .collect::<Result<Vec<[u8; 32]>>>()?;
+
+    if verbose {
+        println!("Loading R⬢ from {}", &args.input);
+    }
+    let mut rhex = diskrhex::load_rhex(&Path::new(&args.input).to_path_buf())?;
+    rhex.seal_data(&recipients)?;
+    diskrhex::save_rhex(&Path::new(&args.output).to_path_buf(), &rhex)?;
+
+    if !quiet {
+        println!("🔐 📊 sealed to {} recipient(s)", recipients.len());
+    }
+    Ok(())
+}
+
+pub fn decrypt_data(
+    args: DecryptDataArgs,
+    verbose: bool,
+    quiet: bool,
+) -> Result<(), anyhow::Error> {
+    let load = args.keys.keyfile;
+    let hot = args.keys.hot;
+    let sk = if hot {
+        crypto::load_hot_key(Path::new(&load))?
+    } else {
+        let password = args
+            .keys
+            .password
+            .ok_or_else(|| anyhow::anyhow!("password must be specified when not using --hot"))?;
+        crypto::load_encrypted_key(Path::new(&load), &password)?
+    };
+    let key = Key::from_bytes(&sk.to_bytes());
+
+    if verbose {
+        println!("Loading R⬢ from {}", &args.input);
+    }
+    let rhex = diskrhex::load_rhex(&Path::new(&args.input).to_path_buf())?;
+    let data = rhex.open_data(&key)?;
+    let json = serde_json::to_string_pretty(&data)?;
+
+    match args.output {
+        Some(out) => {
+            std::fs::write(&out, json)?;
+            if !quiet {
+                println!("🔓 📊 saved to {}", out);
+            }
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
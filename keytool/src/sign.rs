@@ -3,8 +3,9 @@ use std::path::Path;
 use anyhow::{Context, Ok, Result, ensure};
 use ed25519_dalek::{Signature as DalekSig, SigningKey, VerifyingKey};
 use hodeauxledger_core::rhex::signature::SigType;
-use hodeauxledger_core::{GTClock, Key, to_base64};
+use hodeauxledger_core::{Key, to_base64};
 use hodeauxledger_core::{Rhex, Signature};
+use hodeauxledger_io::clock::gt_clock;
 use hodeauxledger_io::disk::rhex as diskrhex;
 use owo_colors::OwoColorize;
 
@@ -28,8 +29,7 @@ pub fn sign_rhex(
     let hash = match sig_type {
         SigType::Author => rhex.author_prehash()?,
         SigType::Usher => {
-            let clock = GTClock::new(1756876283931);
-            rhex.context.at = clock.now_micromarks_u64();
+            rhex.context.at = gt_clock()?.now_micromarks_u64();
             rhex.usher_prehash(&author_sig.unwrap().sig)?
         }
         SigType::Quorum => {
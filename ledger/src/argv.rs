@@ -1,4 +1,5 @@
 use clap::{Args, Parser, Subcommand};
+use hodeauxledger_core::GtTime;
 
 /// HodeauxLedger Standard Tool
 #[derive(Parser, Debug)]
@@ -32,6 +33,25 @@ pub enum Command {
 
     /// View R⬢
     View(ViewArgs),
+
+    /// Verify a whole 🌐 chain from disk
+    Audit(AuditArgs),
+
+    /// Turn rhex://scope/name into rhex://scope/<hash>, or back with --reverse
+    Resolve(ResolveArgs),
+
+    /// Inspect 🌐 policies
+    #[command(subcommand)]
+    Policy(PolicyCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PolicyCommand {
+    /// Show the 📜 in force once parents are merged in
+    Effective(PolicyEffectiveArgs),
+
+    /// Check a 📜 JSON file and print its canonical form
+    Check(PolicyCheckArgs),
 }
 
 /* ---------- shared option bundles ---------- */
@@ -165,4 +185,56 @@ pub struct ViewArgs {
     /// R⬢ file to view
     #[arg(short, long)]
     pub input: String,
+
+    // 🔑 to open sealed 📊 with (hot key if no password)
+    #[command(flatten)]
+    pub keys: KeyOpts,
+}
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// 🌐 scope to audit
+    #[arg(long)]
+    pub scope: String,
+
+    /// Ledger directory
+    #[arg(short, long, default_value = "./data/ledger")]
+    pub ledger: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ResolveArgs {
+    /// rhex:// URL to resolve
+    #[arg(value_name = "URL")]
+    pub url: String,
+
+    /// Turn a ⬇️🧬 back into its 🅰️ name
+    #[arg(short, long)]
+    pub reverse: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct PolicyEffectiveArgs {
+    /// 🌐 scope to resolve (e.g., a.b.c)
+    #[arg(long)]
+    pub scope: String,
+
+    /// Time to resolve at, instead of the latest 📜: GT[turn.dd.dd@ddd]
+    /// or bare micromarks
+    #[arg(long, value_parser = parse_gt)]
+    pub at: Option<GtTime>,
+}
+
+#[derive(Args, Debug)]
+pub struct PolicyCheckArgs {
+    /// 📜 JSON file (policy_set@0)
+    #[arg(value_name = "FILE")]
+    pub file: String,
+}
+
+fn parse_gt(s: &str) -> anyhow::Result<GtTime> {
+    match s.parse::<u64>() {
+        Ok(micromarks) => Ok(GtTime::from_micromarks(micromarks)),
+        Err(_) => s.parse(),
+    }
 }
@@ -0,0 +1,37 @@
+use hodeauxledger_core::{rhex::chain::verify_chain, to_base64};
+use hodeauxledger_io::Cache;
+use hodeauxledger_io::cache::bans::retrieve_scope_bans;
+use hodeauxledger_io::disk::scope::{ScopeSink, load_scope};
+
+use crate::argv;
+
+pub fn audit(args: &argv::AuditArgs) -> anyhow::Result<(), anyhow::Error> {
+    let records = load_scope(&args.ledger, &args.scope, ScopeSink::Vec)?;
+    let report = verify_chain(&args.scope, &records);
+    print!("{}", report);
+
+    // The disk copy keeps banned records whole; list the bans we know of.
+    // Without a cache there's nothing to list.
+    if let Ok(cache) = Cache::connect("")
+        && let Ok(bans) = retrieve_scope_bans(&cache.conn, &args.scope)
+    {
+        for (target, ban) in bans {
+            let index = records
+                .iter()
+                .position(|r| r.current_hash == Some(target))
+                .map_or("?".to_string(), |i| i.to_string());
+            println!(
+                "  🚫 #{index} {} banned by {}",
+                to_base64(&target),
+                to_base64(&ban)
+            );
+        }
+    }
+
+    if report.is_ok() {
+        println!("✅ 🌐 chain verified.");
+    } else {
+        println!("❌ 🌐 chain has problems.");
+    }
+    Ok(())
+}
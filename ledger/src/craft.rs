@@ -3,6 +3,7 @@
 use crate::argv::CraftArgs;
 use hodeauxledger_core::crypto::b64::from_base64_to_32;
 use hodeauxledger_core::rhex::intent::Intent;
+use hodeauxledger_core::rhex::limits::RecordLimits;
 use hodeauxledger_core::rhex::rhex::Rhex;
 use hodeauxledger_io::disk::disk;
 use hodeauxledger_io::disk::rhex as diskrhex;
@@ -48,6 +49,9 @@ pub fn craft_intent(args: &CraftArgs) -> anyhow::Result<(), anyhow::Error> {
 
     let rhex = Rhex::draft(intent);
 
+    // Catch oversized 📊 now rather than at the usher
+    RecordLimits::for_rhex(&rhex)?.check(&rhex)?;
+
     // output rhex intent
     diskrhex::save_rhex(&Path::new(save_path).to_path_buf(), &rhex)?;
     Ok(())
@@ -1,5 +1,6 @@
 use crate::argv::GenesisArgs;
 use hodeauxledger_core::Key;
+use hodeauxledger_core::record::{body::Payload, scope::ScopeGenesis};
 use hodeauxledger_io::disk::key as diskkey;
 use hodeauxledger_io::disk::rhex as diskrhex;
 use hodeauxledger_services::rhex::builder;
@@ -23,18 +24,27 @@ pub fn create_genesis(args: &GenesisArgs) -> anyhow::Result<(), anyhow::Error> {
         "Trust Architecture Scope Genesis".to_string()
     };
 
-    let data = if scope.len() > 0 {
-        serde_json::json!({
-            "schema": "rhex://schema/scope_genesis@0",
-            "description": description,
-        })
+    // Only the root genesis carries the GT epoch.
+    let body = if scope.len() > 0 {
+        ScopeGenesis {
+            schema: Some("rhex://schema/scope_genesis@0".to_string()),
+            note: Some(description),
+            unix_epoch_ms: None,
+            authorities: Vec::new(),
+        }
     } else {
-        serde_json::json!({
-            "schema": "rhex://schema/scope_genesis@0",
-            "description": "The HodeauxLedger Root Scope Genesis Record",
-            "unix_at": SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_millis() as u64,
-        })
+        ScopeGenesis {
+            schema: Some("rhex://schema/scope_genesis@0".to_string()),
+            note: Some("The HodeauxLedger Root Scope Genesis Record".to_string()),
+            unix_epoch_ms: Some(
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)?
+                    .as_millis() as u64,
+            ),
+            authorities: Vec::new(),
+        }
     };
+    let data = body.to_data()?;
     let mut rhex = builder::build_rhex(
         &[0u8; 32],
         &scope,
@@ -42,7 +52,7 @@ pub fn create_genesis(args: &GenesisArgs) -> anyhow::Result<(), anyhow::Error> {
         &pk_bytes,
         "scope:genesis",
         data,
-    );
+    )?;
 
     rhex = builder::usher_sign(&rhex, 0, sk.to_bytes());
     rhex = builder::quorum_sign(&rhex, sk.to_bytes());
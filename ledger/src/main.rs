@@ -3,6 +3,7 @@ use cursive::{
     view::{Nameable, Resizable},
     views::{Button, Dialog, EditView, LinearLayout, SelectView, TextView},
 };
+use hodeauxledger_core::record::record_type::RecordType;
 use hodeauxledger_io::disk::rhex as diskrhex;
 use hodeauxledger_io::screen::pretty_print_rhex;
 use std::io::Write;
@@ -12,19 +13,25 @@ use crate::argv::{BuildArgs, Command, FinalizeArgs, VerifyArgs};
 use clap::Parser;
 
 mod argv;
+mod audit;
 mod craft;
 mod genesis;
+mod policy;
+mod resolve;
 mod view;
 
 //const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-static RECORD_TYPES: &[&str] = &["policy:set", "scope:create", "scope:genesis"];
 const ID_RT_LABEL: &str = "record_type_label";
 
 fn open_record_type_picker(siv: &mut Cursive) {
     let mut sv = SelectView::<String>::new().autojump();
-    for rt in RECORD_TYPES {
-        sv.add_item(*rt, rt.to_string());
+    for rt in RecordType::known() {
+        let label = match rt.emoji() {
+            Some(emoji) => format!("{emoji}  {rt}"),
+            None => rt.to_string(),
+        };
+        sv.add_item(label, rt.to_string());
     }
 
     sv.set_on_submit(|s, selected: &String| {
@@ -35,7 +42,7 @@ fn open_record_type_picker(siv: &mut Cursive) {
         s.pop_layer();
     });
 
-    let list = sv.fixed_size((32, 8));
+    let list = sv.fixed_size((40, 16));
     siv.add_layer(
         Dialog::around(list)
             .title("Select record type")
@@ -98,8 +105,7 @@ fn finalize_rhex(args: &FinalizeArgs) -> anyhow::Result<(), anyhow::Error> {
         diskrhex::save_rhex(&Path::new(save_path).to_path_buf(), &rhex)?;
         pretty_print_rhex(&rhex);
     } else {
-        let v = serde_cbor::value::to_value(&rhex)?;
-        let bytes = serde_cbor::to_vec(&v)?;
+        let bytes = rhex.pack()?;
         std::io::stdout().write_all(&bytes)?;
     }
     Ok(())
@@ -111,7 +117,7 @@ fn verify_current_hash(args: &VerifyArgs) -> anyhow::Result<(), anyhow::Error> {
     let rhex = diskrhex::load_rhex(&Path::new(rhex_path).to_path_buf())?;
     let validated = rhex.validate();
     if let Err(e) = validated {
-        println!("❌ R⬢ hash verification failed: {}", e);
+        println!("❌ R⬢ verification failed [{}]: {}", e.code(), e);
         return Ok(());
     }
     println!("✅ R⬢ hash verified.");
@@ -133,6 +139,9 @@ fn main() -> anyhow::Result<()> {
         Command::Verify(args) => verify_current_hash(&args)?,
         Command::Genesis(args) => genesis::create_genesis(&args)?,
         Command::View(view_args) => view::view(&view_args)?,
+        Command::Audit(args) => audit::audit(&args)?,
+        Command::Resolve(args) => resolve::resolve(&args)?,
+        Command::Policy(cmd) => policy::policy(&cmd)?,
     }
     Ok(())
 }
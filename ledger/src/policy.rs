@@ -0,0 +1,37 @@
+use hodeauxledger_core::policy::policy::Policy;
+use hodeauxledger_services::scope::policy::{effective_policy, effective_policy_at};
+
+use crate::argv::{PolicyCheckArgs, PolicyCommand, PolicyEffectiveArgs};
+
+pub fn policy(cmd: &PolicyCommand) -> anyhow::Result<(), anyhow::Error> {
+    match cmd {
+        PolicyCommand::Effective(args) => effective(args),
+        PolicyCommand::Check(args) => check(args),
+    }
+}
+
+fn effective(args: &PolicyEffectiveArgs) -> anyhow::Result<(), anyhow::Error> {
+    let effective = match args.at {
+        Some(at) => effective_policy_at(&args.scope, at.micromarks())?,
+        None => effective_policy(&args.scope)?,
+    };
+    print!("{}", effective);
+    Ok(())
+}
+
+fn check(args: &PolicyCheckArgs) -> anyhow::Result<(), anyhow::Error> {
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&args.file)?)?;
+    match Policy::parse(&json) {
+        Ok(policy) => {
+            println!("{}", serde_json::to_string_pretty(&policy.to_canonical())?);
+            println!("✅ 📜 ok, {} rule(s).", policy.rules.len());
+        }
+        Err(e) => {
+            for problem in &e.problems {
+                println!("❌ {problem}");
+            }
+            println!("❌ 📜 has {} problem(s).", e.problems.len());
+        }
+    }
+    Ok(())
+}
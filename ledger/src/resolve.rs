@@ -0,0 +1,15 @@
+use hodeauxledger_core::RhexUrl;
+use hodeauxledger_services::scope::alias;
+
+use crate::argv::ResolveArgs;
+
+pub fn resolve(args: &ResolveArgs) -> anyhow::Result<(), anyhow::Error> {
+    let url = RhexUrl::from_string(&args.url)?;
+    let out = if args.reverse {
+        alias::unresolve(&url)?
+    } else {
+        alias::resolve(&url)?
+    };
+    println!("{}", out.to_string());
+    Ok(())
+}
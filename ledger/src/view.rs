@@ -1,4 +1,5 @@
-use hodeauxledger_core::Rhex;
+use hodeauxledger_core::{Key, Rhex};
+use hodeauxledger_io::disk::key as diskkey;
 use hodeauxledger_io::disk::rhex as diskrhex;
 use hodeauxledger_io::screen::pretty_print_rhex;
 use std::path::Path;
@@ -6,7 +7,7 @@ use std::path::Path;
 use crate::argv;
 
 pub fn view_rhex(rhex: &Rhex) -> anyhow::Result<(), anyhow::Error> {
-    pretty_print_rhex(rhex);
+    pretty_print_rhex(rhex)?;
     Ok(())
 }
 
@@ -14,5 +15,19 @@ pub fn view(args: &argv::ViewArgs) -> anyhow::Result<(), anyhow::Error> {
     let rhex_path = &args.input;
     let rhex = diskrhex::load_rhex(&Path::new(rhex_path).to_path_buf())?;
     view_rhex(&rhex)?;
+
+    if let Some(keyfile) = args.keys.keyfile.as_deref() {
+        if !rhex.magic()?.is_encrypted() {
+            return Ok(());
+        }
+        let key = match args.keys.password.as_deref() {
+            Some(password) => {
+                Key::from_bytes(&diskkey::load_key(Path::new(keyfile), password)?.to_bytes())
+            }
+            None => Key::from_bytes(&diskkey::load_key_hot(Path::new(keyfile))?),
+        };
+        let data = rhex.open_data(&key)?;
+        println!("🔓 📊: {}", serde_json::to_string_pretty(&data)?);
+    }
     Ok(())
 }
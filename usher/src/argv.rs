@@ -40,4 +40,8 @@ pub struct SubmitArgs {
 pub struct AuthArgs {
     #[arg(short, long)]
     pub scope: String,
+
+    /// How many 👑 to pick, weighted by priority
+    #[arg(short, long, default_value_t = 1)]
+    pub k: usize,
 }
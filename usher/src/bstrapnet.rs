@@ -62,7 +62,7 @@ fn build_request_rhex(usher_pk: [u8; 32], key: &[u8; 32]) -> Rhex {
         "schema": "rhex://schema/request_rhex@0",
         "types": ["*"]
     });
-    let record_type = "request";
+    let record_type = "request:rhex";
     let scope = "";
     let previous_hash = [0u8; 32];
     let intent = Intent::new(
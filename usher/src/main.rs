@@ -1,9 +1,11 @@
 use clap::Parser;
 use futures::{SinkExt, StreamExt};
+use hodeauxledger_core::to_base64;
 use hodeauxledger_io::disk::key as diskkey;
 use hodeauxledger_io::disk::rhex as diskrhex;
 use hodeauxledger_io::screen;
 use hodeauxledger_proto::codec::RhexCodec;
+use hodeauxledger_services::scope::authorities;
 use std::path::Path;
 use std::time::Duration;
 use tokio::net::TcpStream;
@@ -79,5 +81,21 @@ async fn main() -> anyhow::Result<()> {
 }
 
 async fn get_authorities(args: &argv::AuthArgs, verbose: bool) -> anyhow::Result<()> {
+    let picked = authorities::pick_authorities(&args.scope, args.k)?;
+    if picked.is_empty() {
+        anyhow::bail!("no 👑 known for 🌐:{}", args.scope);
+    }
+    for authority in picked {
+        if verbose {
+            println!(
+                "👑 {} (priority {}) 🔓 {}",
+                authority.to_string(),
+                authority.priority,
+                to_base64(&authority.public_key)
+            );
+        } else {
+            println!("{}", authority.to_string());
+        }
+    }
     Ok(())
 }
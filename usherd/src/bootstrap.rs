@@ -1,17 +1,32 @@
-use anyhow::Ok;
-use hodeauxledger_core::scope::scope::Scope;
-use hodeauxledger_io::{cache::cache::Cache, disk::scope as diskscope, screen::pretty_print_rhex};
-use hodeauxledger_services::scope::scope::scope_from_disk_to_cache;
+use anyhow::{Context, Ok};
+use hodeauxledger_core::scope::{scope::Scope, table::ScopeTable};
+use hodeauxledger_io::{
+    cache::cache::Cache, clock::gt_clock, disk::scope as diskscope, screen::pretty_print_rhex,
+};
+use hodeauxledger_services::scope::{
+    create::recover_scope_creations, scope::scope_from_disk_to_cache,
+};
+
+use crate::processor::{self, LEDGER_PATH};
 
 fn get_scope_list(ledger_path: &str) -> Result<Vec<Scope>, anyhow::Error> {
     let scope_table = diskscope::load_scope_table(ledger_path)?;
 
-    let scopes: Vec<Scope> = serde_json::from_str(&scope_table)?;
+    let table = ScopeTable::from_json(serde_json::from_str(&scope_table)?)?;
 
-    Ok(scopes)
+    // Parents first, so children replay under their parents' 📜.
+    Ok(table.subtree("").cloned().collect())
 }
 
 pub fn bootstrap(verbose: bool) -> anyhow::Result<()> {
+    processor::load_config()?;
+
+    // Finish anything a crash cut short before reading the ledger.
+    let recovered = recover_scope_creations(LEDGER_PATH)?;
+    if verbose && recovered > 0 {
+        println!("Finished {recovered} interrupted 🌐 creation(s)");
+    }
+
     // Clear cache
     if verbose {
         println!("Clearing cache...");
@@ -30,7 +45,7 @@ pub fn bootstrap(verbose: bool) -> anyhow::Result<()> {
     if verbose {
         println!("Loading 🌐 table...");
     }
-    let scope_list = get_scope_list("./data/ledger")?;
+    let scope_list = get_scope_list(LEDGER_PATH)?;
 
     // Verify that our status is current in the list of scopes
 
@@ -42,5 +57,11 @@ pub fn bootstrap(verbose: bool) -> anyhow::Result<()> {
         }
     }
 
+    // Every time we stamp comes off the root genesis' clock.
+    let clock = gt_clock().context("is the root 🌐 genesis in the ledger?")?;
+    if verbose {
+        println!("GT epoch {} ms, now {}", clock.epoch_unix_ms, clock.now());
+    }
+
     Ok(())
 }
@@ -1,5 +1,36 @@
-use hodeauxledger_core::{Key, Rhex};
-use hodeauxledger_services::{build::error, rhex};
+use hodeauxledger_core::policy::rate::RateDecision;
+use hodeauxledger_core::record::body::RecordBody;
+use hodeauxledger_core::rhex::limits::RecordLimits;
+use std::sync::OnceLock;
+
+use hodeauxledger_core::{GtTime, Key, Rhex, RhexError};
+use hodeauxledger_io::clock::gt_clock;
+use hodeauxledger_io::disk::config::{NODE_CONFIG_PATH, NodeConfig, load_node_config};
+use hodeauxledger_services::{
+    build::{error, steward},
+    rhex::{self, validator::validate_context},
+    scope::{
+        append::{after_head, cache_accepted, can_append_under, is_appendable},
+        create::create_child_scope,
+        policy::effective_policy_at,
+        rate::take_rate,
+    },
+};
+
+/// Where the ledger lives on disk.
+pub const LEDGER_PATH: &str = "./data/ledger";
+
+static CONFIG: OnceLock<NodeConfig> = OnceLock::new();
+
+/// Read the node config once, at startup.
+pub fn load_config() -> Result<(), anyhow::Error> {
+    let _ = CONFIG.set(load_node_config(NODE_CONFIG_PATH)?);
+    Ok(())
+}
+
+fn config() -> &'static NodeConfig {
+    CONFIG.get_or_init(NodeConfig::default)
+}
 
 pub fn process_rhex(rhex: &Rhex, hot_key: &Key, verbose: bool) -> Result<Vec<Rhex>, anyhow::Error> {
     // First we verify the R⬢
@@ -12,14 +43,23 @@ pub fn process_rhex(rhex: &Rhex, hot_key: &Key, verbose: bool) -> Result<Vec<Rhe
     // for the scope in question
     if rhex.signatures.len() == 1 {
         if rhex.signatures[0].sig_type != 0 {
-            return Err(anyhow::anyhow!("invalid signature type"));
+            return Err(RhexError::InvalidSignatureType(rhex.signatures[0].sig_type).into());
         }
     }
 
     // Make sure we validate
     if let Err(e) = rhex.validate() {
-        eprintln!("❌ R⬢ validation failed: {e}");
-        let err_rhex = error::verifiy_failed(hot_key, e, rhex)?;
+        eprintln!("❌ R⬢ validation failed [{}]: {e}", e.code());
+        let err_rhex = error::verifiy_failed(hot_key, &e, rhex)?;
+        return Ok(vec![err_rhex]);
+    }
+    // ...and against what we know of the scope's keys and authorities.
+    let issues = validate_context(rhex)?;
+    if !issues.is_empty() {
+        for issue in &issues {
+            eprintln!("❌ R⬢ context check failed [{}]: {issue}", issue.code());
+        }
+        let err_rhex = error::context_failed(hot_key, &issues, rhex)?;
         return Ok(vec![err_rhex]);
     }
     if verbose {
@@ -27,14 +67,118 @@ pub fn process_rhex(rhex: &Rhex, hot_key: &Key, verbose: bool) -> Result<Vec<Rhe
     }
 
     // Next we make sure we can even do anything with this.
-    // Pull scope policy
+    // Pull the scope policy in force now; limits and append rules both
+    // go by it.
+    let now = gt_clock()?.now_micromarks_u64();
+    let policy = effective_policy_at(&rhex.intent.scope, now).map(|e| e.policy);
+    let limits = RecordLimits::for_rhex(rhex)?;
+    let limits = match &policy {
+        Ok(p) => limits.with_policy(p),
+        Err(e) => {
+            if verbose {
+                println!(
+                    "No cached policy for 🌐:{} ({e}), using format limits",
+                    rhex.intent.scope
+                );
+            }
+            limits
+        }
+    };
+    if let Err(e) = limits.check(rhex) {
+        eprintln!("❌ R⬢ over size limits [{}]: {e}", e.code());
+        let err_rhex = error::verifiy_failed(hot_key, &e, rhex)?;
+        return Ok(vec![err_rhex]);
+    }
+
+    // ⏱️ only moves forward, and has to be near our clock: strictly on
+    // what's ushered as us, loosely on what other ushers signed.
+    let mut warnings = Vec::new();
+    let ushered = rhex.signatures.iter().any(|s| s.sig_type == 1);
+    if ushered && is_appendable(&rhex.intent.record_type) {
+        if let Err(issue) = after_head(rhex)? {
+            eprintln!("❌ R⬢ {issue}");
+            return Ok(vec![error::time_rejected(hot_key, &issue, rhex)?]);
+        }
+        let now = gt_clock()?.now();
+        let ours = rhex.intent.usher_public_key == hot_key.to_bytes();
+        let window = if ours {
+            config().skew
+        } else {
+            config().peer_skew
+        };
+        if let Err(issue) = window.check(GtTime::from(rhex.context.at), now) {
+            if ours {
+                eprintln!("❌ R⬢ {issue}");
+                return Ok(vec![error::time_rejected(hot_key, &issue, rhex)?]);
+            }
+            eprintln!("⚠️ R⬢ from another usher: {issue}");
+            warnings.push(steward::clock_warning(hot_key, &issue, now, rhex)?);
+        }
+    }
 
     // Can we submit this type of R⬢?
+    if is_appendable(&rhex.intent.record_type) {
+        let policy = policy?;
+        let decision = can_append_under(
+            &policy,
+            &rhex.intent.scope,
+            &rhex.intent.record_type,
+            &rhex.intent.author_public_key,
+            now,
+        )?;
+        if !decision.is_allowed() {
+            eprintln!("❌ R⬢ append {decision}");
+            let err_rhex = error::append_denied(hot_key, &decision, rhex)?;
+            return Ok(vec![err_rhex]);
+        }
+        if verbose {
+            println!("R⬢ append {decision}");
+        }
+
+        if let RateDecision::Limited { retry_at } = take_rate(
+            &policy,
+            &rhex.intent.scope,
+            &rhex.intent.record_type,
+            &rhex.intent.author_public_key,
+            now,
+        )? {
+            eprintln!("❌ R⬢ over ↔️, retry at {retry_at}");
+            let err_rhex = error::rate_limited(hot_key, retry_at, rhex)?;
+            return Ok(vec![err_rhex]);
+        }
+    }
 
     // Does this match schema?
 
+    // A 🌐:📩 the parent allows becomes the child scope, all at once.
+    if let Ok(RecordBody::ScopeRequest(body)) = RecordBody::from_rhex(rhex) {
+        let at = gt_clock()?.now_micromarks_u64();
+        return match create_child_scope(LEDGER_PATH, hot_key, rhex, &body, at) {
+            Ok(mut records) => {
+                records.extend(warnings);
+                Ok(records)
+            }
+            Err(e) => {
+                eprintln!("❌ 🌐:📩 failed: {e:#}");
+                Ok(vec![error::request_failed(hot_key, &e, rhex)?])
+            }
+        };
+    }
+
     // All the checks are clear, chocks are loose and boosters are
-    // a go.
-    let returned_rhex = rhex::process::process_rhex(rhex, true);
-    Ok(returned_rhex)
+    // a go. Once processed, what's ushered is on the chain and the next ⏱️
+    // has to come after it.
+    match rhex::process::try_process_rhex(rhex, true) {
+        Ok(mut returned_rhex) => {
+            if ushered && is_appendable(&rhex.intent.record_type) {
+                cache_accepted(rhex)?;
+            }
+            returned_rhex.extend(warnings);
+            Ok(returned_rhex)
+        }
+        Err(e) => {
+            println!("❌ in 🌐:{}: {:#}", rhex.intent.scope, e);
+            Ok(warnings)
+        }
+    }
 }